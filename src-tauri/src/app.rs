@@ -1,59 +1,83 @@
 use std::sync::Arc;
 
-use crate::backend::AppleService;
-use crate::backend::DeezerClient;
+use crate::backend::backend::BackendClient;
+use crate::backend::environment::{
+    load_environment, resolve_base_url, save_environment, EnvironmentConfig,
+};
+use crate::backend::BackendEnvironment;
+use crate::backend::ConnectivityStatus;
 use crate::backend::NotificationService;
 use crate::backend::PlaylistService;
-use crate::backend::SpotifyClient;
 use crate::backend::UserService;
-use crate::backend::YoutubeClient;
-use crate::error::AppResult;
+use crate::backend::ENVIRONMENT_CHANGED_EVENT;
+use crate::backend::{
+    AuthenticatedUser, FriendInvite, FriendRequest, FriendRequestDirection, ReportUserRequest,
+    UserPage,
+};
+use crate::backend::{CreateShareLinkRequest, ShareLink};
+use crate::backend::{
+    OAuthStates, PlatformAuthFailed, PlatformAuthFailure, PLATFORM_AUTH_FAILED_EVENT,
+};
+use crate::backend::{
+    PlatformConnection, PlatformContext, PlatformRegistry, PLATFORM_CONNECTION_CHANGED_EVENT,
+};
+use crate::deep_link::{DeepLinkRoute, DeepLinkRouter, DEEP_LINK_EVENT};
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::models::ErrorNotification;
 use log::error;
 use log::info;
+use log::warn;
 
-use swaptun_backend::AddFriendRequest;
 use swaptun_backend::GetUsersRequest;
 use swaptun_backend::RemoveFriendRequest;
 use swaptun_backend::UserBean;
 use swaptun_backend::{
-    AddTokenRequest, CreateUserRequest, ForgotPasswordRequest, GetPlaylistMusicsResponse,
-    GetPlaylistResponse, GetPlaylistsParams, LoginEmailRequest, LoginRequest, LoginResponse,
-    PlaylistOrigin, RegisterFcmTokenRequest, ResetPasswordRequest, SendPlaylistRequest,
-    SendPlaylistResponse, SendTestNotificationRequest, SharePlaylistRequest,
-    SharedPlaylistsResponse, SpotifyUrlResponse, VerifyTokenRequest, VerifyTokenResponse,
+    CreateUserRequest, ForgotPasswordRequest, GetPlaylistMusicsResponse, GetPlaylistResponse,
+    LoginEmailRequest, LoginRequest, PlaylistOrigin, RegisterFcmTokenRequest, ResetPasswordRequest,
+    SendPlaylistRequest, SendPlaylistResponse, SendTestNotificationRequest, SharePlaylistRequest,
+    SharedPlaylistsResponse, VerifyTokenRequest, VerifyTokenResponse,
 };
 use tauri::async_runtime::Mutex;
 use tauri::http::StatusCode;
 use tauri::AppHandle;
 use tauri::Emitter;
 use tauri::Url;
-use tauri_plugin_custom_tabs_manager::{CustomTabsManagerExt, OpenCustomTabSimpleRequest};
-use tauri_plugin_musickit::AuthorizationResponse;
-use tauri_plugin_musickit::MusicKitExt;
+
 pub struct App {
     app_handle: AppHandle,
-    spotify_client: SpotifyClient,
-    _deezer_client: DeezerClient,
+    backend_client: Arc<BackendClient>,
     user_service: UserService,
     playlist_service: PlaylistService,
-    youtube_service: YoutubeClient,
     notification_service: NotificationService,
-    apple_service: AppleService,
+    platforms: PlatformRegistry,
     ready: Mutex<bool>,
+    /// Deep links received before the frontend was ready to route them
+    pending_links: Mutex<Vec<DeepLinkRoute>>,
+    /// Invite opened while logged out, accepted once the user logs in
+    pending_invite: Mutex<Option<String>>,
+    /// Playlist share link opened while logged out, opened once the user is logged in
+    pending_share_link: Mutex<Option<String>>,
 }
 
 impl App {
     pub fn new(app_handle: AppHandle) -> Arc<Self> {
+        let backend_client = Arc::new(BackendClient::new(app_handle.clone()));
+        let context = PlatformContext {
+            backend_client: backend_client.clone(),
+            app_handle: app_handle.clone(),
+            oauth: Arc::new(OAuthStates::new(backend_client.clone())),
+        };
         let instance = Self {
             app_handle: app_handle.clone(),
-            spotify_client: SpotifyClient::new(app_handle.clone()),
-            _deezer_client: DeezerClient::new(app_handle.clone()),
-            user_service: UserService::new(app_handle.clone()),
-            playlist_service: PlaylistService::new(app_handle.clone()),
-            youtube_service: YoutubeClient::new(app_handle.clone()),
-            notification_service: NotificationService::new(app_handle.clone()),
-            apple_service: AppleService::new(app_handle.clone()),
+            backend_client: backend_client.clone(),
+            user_service: UserService::new(backend_client.clone()),
+            playlist_service: PlaylistService::new(backend_client.clone()),
+            notification_service: NotificationService::new(backend_client.clone()),
+            platforms: PlatformRegistry::new(context),
             ready: Mutex::new(false),
+            pending_links: Mutex::new(Vec::new()),
+            pending_invite: Mutex::new(None),
+            pending_share_link: Mutex::new(None),
         };
         let instance = Arc::new(instance);
         instance
@@ -62,20 +86,75 @@ impl App {
         &self.app_handle
     }
 
-    pub async fn get_autorization_url_spotify(&self) -> AppResult<SpotifyUrlResponse> {
-        self.spotify_client.get_auth_url().await
+    pub fn get_connectivity_status(&self) -> ConnectivityStatus {
+        ConnectivityStatus {
+            online: self.backend_client.is_online(),
+        }
+    }
+
+    pub fn get_backend_environment(&self) -> BackendEnvironment {
+        let config = EnvironmentConfig::load(&self.app_handle);
+        load_environment(&self.app_handle, &config)
+    }
+
+    /// Switch backend environment, persist it and let every client rebuild itself
+    pub fn set_backend_environment(&self, environment: BackendEnvironment) -> AppResult<()> {
+        let config = EnvironmentConfig::load(&self.app_handle);
+        let url = resolve_base_url(&self.app_handle, &config, &environment)?;
+        save_environment(&self.app_handle, &environment)?;
+        info!(
+            "Switching backend environment to {:?} ({})",
+            environment, url
+        );
+        // The session belongs to the previous backend and must not reach the new host
+        if self.backend_client.has_session() {
+            self.backend_client
+                .expire_session("Backend environment changed");
+        }
+        self.app_handle
+            .emit(ENVIRONMENT_CHANGED_EVENT, environment)
+            .map_err(|e| {
+                AppError::Internal(format!("Failed to emit environment change: {}", e).into())
+            })
+    }
+
+    /// Abort a long-running request started by the frontend
+    pub fn cancel_request(&self, request_id: &str) -> bool {
+        self.backend_client.cancel(request_id)
     }
 
     pub async fn register(&self, request: CreateUserRequest) -> AppResult<StatusCode> {
         self.user_service.register(request).await
     }
 
-    pub async fn login(&self, request: LoginRequest) -> AppResult<LoginResponse> {
-        self.user_service.login(request).await
+    pub async fn login(&self, request: LoginRequest) -> AppResult<AuthenticatedUser> {
+        let user = self.user_service.login(request).await?;
+        self.accept_pending_invite().await;
+        Ok(user)
     }
 
-    pub async fn login_email(&self, request: LoginEmailRequest) -> AppResult<LoginResponse> {
-        self.user_service.login_email(request).await
+    pub async fn login_email(&self, request: LoginEmailRequest) -> AppResult<AuthenticatedUser> {
+        let user = self.user_service.login_email(request).await?;
+        self.accept_pending_invite().await;
+        Ok(user)
+    }
+
+    /// Log out on the backend and the device, then let the frontend reset itself
+    pub async fn logout(&self) -> AppResult<()> {
+        if let Err(e) = self.notification_service.unregister_fcm_token().await {
+            error!("Failed to unregister FCM token: {}", e);
+        }
+        // Like the FCM failure above, a vault that could not be wiped must not keep the user logged in
+        if let Err(e) = self.user_service.logout().await {
+            error!("Failed to clear local credentials: {}", e);
+        }
+        self.pending_links.lock().await.clear();
+        self.pending_invite.lock().await.take();
+        self.pending_share_link.lock().await.take();
+
+        self.app_handle
+            .emit("logout", "")
+            .map_err(|e| AppError::Internal(format!("Failed to emit logout event: {}", e).into()))
     }
 
     pub async fn verify_token(
@@ -85,167 +164,178 @@ impl App {
         self.user_service.verify_token(request).await
     }
 
-    pub async fn is_app_ready(&self) -> Result<bool, String> {
+    pub async fn is_app_ready(&self) -> bool {
         let ready = self.ready.lock().await;
-        Ok(*ready)
+        *ready
     }
 
+    /// Mark the frontend as ready and route the deep links queued until now
     pub async fn set_app_ready(&self) {
-        let mut ready = self.ready.lock().await;
-        *ready = true;
+        let pending = {
+            let mut ready = self.ready.lock().await;
+            *ready = true;
+            std::mem::take(&mut *self.pending_links.lock().await)
+        };
+
+        if !pending.is_empty() {
+            info!("Routing {} queued deep links", pending.len());
+        }
+        for route in pending {
+            self.route_deep_link(route).await;
+        }
     }
 
     pub async fn handle_open_url(&self, urls: Vec<Url>) {
         info!("deep link URLs: {:?}", urls);
-        if let Some(url) = urls.first() {
-            if url.path() == "/open/spotify" {
-                self.handle_spotify_auth(url).await
-            }
-            if url.path() == "/open/youtube" {
-                self.handle_youtube_auth(url).await
-            }
-            if url.path() == "/reset-password" {
-                let fullurl = format!("{}?{}", url.path(), url.query().unwrap_or(""));
-                self.app_handle
-                    .emit("routing", fullurl)
-                    .expect("Failed to emit routing event");
+        for url in &urls {
+            let route = DeepLinkRouter::parse(url);
+            {
+                let ready = self.ready.lock().await;
+                if !*ready {
+                    info!("App not ready, queueing deep link");
+                    self.pending_links.lock().await.push(route);
+                    continue;
+                }
             }
+            self.route_deep_link(route).await;
         }
     }
 
-    pub async fn handle_spotify_auth(&self, url: &Url) {
-        let params = url.query_pairs();
-        let mut code = None;
-        for (key, value) in params {
-            match key.as_ref() {
-                "code" => code = Some(value.to_string()),
-                _ => {}
+    async fn route_deep_link(&self, route: DeepLinkRoute) {
+        match route {
+            DeepLinkRoute::PlatformCallback { platform, url } => {
+                self.handle_platform_callback(platform, &url).await
             }
-        }
-        if let Some(code) = code {
-            self.send_spotify_token(code.to_string()).await;
-            info!("token send");
-            match self.import_playlist_backend_request().await {
-                Ok(status) => {
-                    info!("import playlist backend request status: {:?}", status);
-                    if status == StatusCode::OK {
-                        info!("playlist imported successfully");
-                    } else {
-                        error!("Failed to import playlist, status: {:?}", status);
-                    }
+            DeepLinkRoute::SharedPlaylist { token } => {
+                if self.backend_client.has_session() {
+                    self.open_share_link(&token).await;
+                } else {
+                    info!("Share link opened while logged out, keeping it for after login");
+                    *self.pending_share_link.lock().await = Some(token);
                 }
-                Err(e) => {
-                    error!("Error importing playlist: {}", e);
+            }
+            DeepLinkRoute::FriendInvite { token } => {
+                if self.backend_client.has_session() {
+                    self.accept_friend_invite(&token).await;
+                } else {
+                    info!("Friend invite opened while logged out, keeping it for after login");
+                    *self.pending_invite.lock().await = Some(token);
                 }
-            };
-            info!("importing playlist");
-            match self.get_playlists_spotify().await {
-                Ok(playlists) => {
-                    info!("playlist imported");
-                    match self.app_handle.emit("spotify_playlists", playlists) {
-                        Ok(_) => info!("spotify_playlists event emitted"),
-                        Err(e) => error!("Error emitting spotify_playlists event: {}", e),
-                    };
+            }
+            DeepLinkRoute::ResetPassword { token } => {
+                let params = token.map(|token| ("token", token));
+                match Url::parse_with_params("swaptun://app/reset-password", params) {
+                    Ok(route) => {
+                        let path = format!("{}?{}", route.path(), route.query().unwrap_or(""));
+                        if let Err(e) = self.app_handle.emit("routing", path) {
+                            error!("Failed to emit routing event: {}", e);
+                        }
+                    }
+                    Err(e) => error!("Failed to build reset password route: {}", e),
                 }
-                Err(e) => {
-                    error!("Error getting playlists: {}", e);
+            }
+            route => {
+                info!(
+                    "Forwarding unhandled deep link to the frontend: {:?}",
+                    route
+                );
+                if let Err(e) = self.app_handle.emit(DEEP_LINK_EVENT, route) {
+                    error!("Failed to emit deep_link event: {}", e);
                 }
             }
         }
     }
 
-    pub async fn handle_youtube_auth(&self, url: &Url) {
-        let params = url.query_pairs();
-        let mut code = None;
-        for (key, value) in params {
-            match key.as_ref() {
-                "code" => code = Some(value.to_string()),
-                _ => {}
-            }
-        }
-        if let Some(code) = code {
-            info!("youtube code: {}", code);
-            match self.set_youtube_token(code.to_string()).await {
-                Ok(status) => {
-                    info!("YouTube token set successfully with status: {:?}", status);
-                }
-                Err(e) => {
-                    error!("Error setting YouTube token: {}", e);
-                }
-            }
-        } else {
-            error!("No 'code' parameter found in the URL");
+    /// Finish linking a platform from its OAuth redirect
+    async fn handle_platform_callback(&self, origin: PlaylistOrigin, url: &Url) {
+        let result = match self.platforms.get(&origin) {
+            Ok(platform) => platform.handle_callback(url).await,
+            Err(_) => Err(PlatformAuthFailure::UnknownFlow),
+        };
+        match result {
+            Ok(()) => info!("{:?} connected", origin),
+            Err(reason) => self.emit_platform_auth_failed(origin.clone(), reason),
         }
+        self.emit_platform_connection_changed(origin).await;
     }
 
-    pub async fn send_spotify_token(self: &Self, token: String) {
-        let req = AddTokenRequest {
-            token: token.to_string(),
-        };
-        match self.spotify_client.add_token(req).await {
-            Ok(status) => {
-                info!("Spotify token set successfully with status: {:?}", status);
-            }
-            Err(e) => {
-                error!("Error setting Spotify token: {}", e);
-            }
-        };
+    pub async fn connect_platform(
+        &self,
+        origin: PlaylistOrigin,
+        request_id: Option<String>,
+    ) -> AppResult<()> {
+        let result = self.platforms.get(&origin)?.connect(request_id).await;
+        self.emit_platform_connection_changed(origin).await;
+        result
     }
 
-    pub async fn import_playlist_backend_request(&self) -> AppResult<StatusCode> {
-        self.spotify_client.import_playlist_backend_request().await
+    pub async fn import_platform_playlists(
+        &self,
+        origin: PlaylistOrigin,
+        request_id: Option<String>,
+    ) -> AppResult<StatusCode> {
+        let result = self
+            .platforms
+            .get(&origin)?
+            .import_playlists(request_id)
+            .await;
+        self.watch_platform_token(origin, result).await
     }
 
-    pub async fn get_playlists_spotify(&self) -> AppResult<GetPlaylistResponse> {
-        let params = GetPlaylistsParams {
-            origin: Some(PlaylistOrigin::Spotify),
-            include_musics: true,
-        };
-        self.playlist_service.get_playlists(params).await
+    pub async fn get_playlists(&self, origin: PlaylistOrigin) -> AppResult<GetPlaylistResponse> {
+        let result = self.platforms.get(&origin)?.list_playlists().await;
+        self.watch_platform_token(origin, result).await
     }
 
-    pub async fn get_playlists_deezer(&self) -> AppResult<GetPlaylistResponse> {
-        let params = GetPlaylistsParams {
-            origin: Some(PlaylistOrigin::Deezer),
-            include_musics: true,
-        };
-        self.playlist_service.get_playlists(params).await
+    pub async fn disconnect_platform(&self, origin: PlaylistOrigin) -> AppResult<StatusCode> {
+        let result = self.platforms.get(&origin)?.disconnect().await;
+        self.emit_platform_connection_changed(origin).await;
+        result
     }
 
-    pub async fn get_playlists_youtube(&self) -> AppResult<GetPlaylistResponse> {
-        let params = GetPlaylistsParams {
-            origin: Some(PlaylistOrigin::YoutubeMusic),
-            include_musics: true,
-        };
-        self.playlist_service.get_playlists(params).await
-    }
-
-    pub async fn connect_youtube(&self) -> AppResult<()> {
-        let url_response = self.youtube_service.get_auth_url().await;
-        match url_response {
-            Ok(response) => {
-                info!("get_auth_url_youtube response: {}", response.url);
-                self.app_handle
-                    .custom_tabs_manager()
-                    .open_custom_tab_simple(OpenCustomTabSimpleRequest {
-                        url: response.url.clone(),
-                        try_native_app: true,
-                    })
-                    .expect("error while opening custom tab");
-                Ok(())
+    pub async fn get_platform_connections(&self) -> AppResult<Vec<PlatformConnection>> {
+        Ok(self.platforms.connections().await)
+    }
+
+    /// A platform call rejected for its token means the connection changed under us
+    async fn watch_platform_token<T>(
+        &self,
+        origin: PlaylistOrigin,
+        result: AppResult<T>,
+    ) -> AppResult<T> {
+        if let Err(e) = &result {
+            if matches!(
+                e.code(),
+                ErrorCode::PlatformNotConnected | ErrorCode::TokenExpired
+            ) {
+                self.emit_platform_connection_changed(origin).await;
             }
-            Err(e) => {
-                error!("Error getting YouTube auth URL: {}", e);
-                Err(e)
+        }
+        result
+    }
+
+    /// Send a platform's current connection to the frontend
+    async fn emit_platform_connection_changed(&self, origin: PlaylistOrigin) {
+        match self.platforms.connection(&origin).await {
+            Ok(connection) => {
+                if let Err(e) = self
+                    .app_handle
+                    .emit(PLATFORM_CONNECTION_CHANGED_EVENT, connection)
+                {
+                    error!("Failed to emit platform_connection_changed event: {}", e);
+                }
             }
+            Err(e) => error!("Error getting {:?} connection: {}", origin, e),
         }
     }
 
-    pub async fn set_youtube_token(&self, token: String) -> AppResult<StatusCode> {
-        let req = AddTokenRequest {
-            token: token.to_string(),
-        };
-        self.youtube_service.add_token(req).await
+    /// Tell the frontend why a platform could not be connected
+    fn emit_platform_auth_failed(&self, platform: PlaylistOrigin, reason: PlatformAuthFailure) {
+        error!("{:?} authorization failed: {:?}", platform, reason);
+        let event = PlatformAuthFailed { platform, reason };
+        if let Err(e) = self.app_handle.emit(PLATFORM_AUTH_FAILED_EVENT, event) {
+            error!("Failed to emit platform_auth_failed event: {}", e);
+        }
     }
 
     pub async fn set_fcm_token(
@@ -270,8 +360,11 @@ impl App {
         &self,
         playlist_id: i32,
         req: SendPlaylistRequest,
+        request_id: Option<String>,
     ) -> AppResult<SendPlaylistResponse> {
-        self.playlist_service.send_playlist(playlist_id, req).await
+        self.playlist_service
+            .send_playlist(playlist_id, req, request_id)
+            .await
     }
 
     pub async fn share_playlist(&self, playlist_id: i32, user_id: i32) -> AppResult<StatusCode> {
@@ -291,12 +384,35 @@ impl App {
         self.user_service.reset_password(token, req).await
     }
 
-    pub async fn search_users(&self, request: GetUsersRequest) -> AppResult<Vec<UserBean>> {
-        self.user_service.get_users(request).await
+    pub async fn search_users(
+        &self,
+        request: GetUsersRequest,
+        cursor: Option<String>,
+    ) -> AppResult<UserPage> {
+        self.user_service.search_users(request, cursor).await
+    }
+
+    pub async fn send_friend_request(&self, user_id: i32) -> AppResult<FriendRequest> {
+        self.user_service.send_friend_request(user_id).await
+    }
+
+    pub async fn get_friend_requests(
+        &self,
+        direction: FriendRequestDirection,
+    ) -> AppResult<Vec<FriendRequest>> {
+        self.user_service.get_friend_requests(direction).await
+    }
+
+    pub async fn accept_friend_request(&self, request_id: i32) -> AppResult<UserBean> {
+        self.user_service.accept_friend_request(request_id).await
+    }
+
+    pub async fn decline_friend_request(&self, request_id: i32) -> AppResult<StatusCode> {
+        self.user_service.decline_friend_request(request_id).await
     }
 
-    pub async fn add_friend(&self, request: AddFriendRequest) -> AppResult<()> {
-        self.user_service.add_friend(request).await
+    pub async fn cancel_friend_request(&self, request_id: i32) -> AppResult<StatusCode> {
+        self.user_service.cancel_friend_request(request_id).await
     }
 
     pub async fn remove_friend(&self, request: RemoveFriendRequest) -> AppResult<()> {
@@ -307,8 +423,68 @@ impl App {
         self.user_service.get_friends().await
     }
 
+    pub async fn block_user(&self, user_id: i32) -> AppResult<StatusCode> {
+        self.user_service.block_user(user_id).await
+    }
+
+    pub async fn unblock_user(&self, user_id: i32) -> AppResult<StatusCode> {
+        self.user_service.unblock_user(user_id).await
+    }
+
+    pub async fn get_blocked_users(&self) -> AppResult<Vec<UserBean>> {
+        self.user_service.get_blocked_users().await
+    }
+
+    pub async fn report_user(
+        &self,
+        user_id: i32,
+        request: ReportUserRequest,
+    ) -> AppResult<StatusCode> {
+        self.user_service.report_user(user_id, request).await
+    }
+
+    pub async fn create_friend_invite(&self) -> AppResult<FriendInvite> {
+        self.user_service.create_invite().await
+    }
+
+    async fn accept_pending_invite(&self) {
+        let token = self.pending_invite.lock().await.take();
+        if let Some(token) = token {
+            self.accept_friend_invite(&token).await;
+        }
+    }
+
+    /// Send a friend request to the owner of an invite link
+    async fn accept_friend_invite(&self, token: &str) {
+        match self.user_service.accept_invite(token).await {
+            Ok(sender) => {
+                info!("Friend request sent to user {} from invite", sender.user_id);
+                if let Err(e) = self.app_handle.emit("friend_invite_accepted", sender) {
+                    error!("Failed to emit friend_invite_accepted event: {}", e);
+                }
+            }
+            Err(e) => {
+                error!("Failed to accept friend invite: {}", e);
+                let error =
+                    ErrorNotification::server_error("This invite link is invalid or has expired");
+                let _ = self.app_handle.emit("error_notification", error);
+            }
+        }
+    }
+
+    /// Playlists shared with the user, minus the ones from users they blocked
     pub async fn get_shared_playlists(&self) -> AppResult<SharedPlaylistsResponse> {
-        self.playlist_service.get_shared_playlists().await
+        let mut response = self.playlist_service.get_shared_playlists().await?;
+        match self.user_service.blocked_user_ids().await {
+            Ok(blocked) => response
+                .shared_playlists
+                .retain(|shared| !blocked.contains(&shared.shared_by.id)),
+            Err(e) => warn!(
+                "Showing shared playlists unfiltered, blocked users unavailable: {}",
+                e
+            ),
+        }
+        Ok(response)
     }
 
     pub async fn mark_shared_playlist_viewed(
@@ -327,75 +503,46 @@ impl App {
         self.playlist_service.get_playlist_musics(playlist_id).await
     }
 
-    pub async fn synchronize_apple_playlists(&self) -> AppResult<StatusCode> {
-        self.apple_service.synchronize_playlists().await
-    }
-
-    pub async fn connect_apple_music(&self) -> AppResult<AuthorizationResponse> {
-        let developer_token = self.apple_service.get_developer_token().await?;
-        self.app_handle
-            .music_kit()
-            .set_developer_token(developer_token.developer_token)?;
-        let auth_response = self.app_handle.music_kit().authorize()?;
-        info!("Apple Music authorization response: {:?}", auth_response);
-        match auth_response.clone().status {
-            val if val == "authorized".to_string() => {
-                info!("Apple Music authorized");
-                let get_user_token_response = self.app_handle.music_kit().get_user_token()?;
-                if let Some(token) = get_user_token_response.token {
-                    info!("Apple Music user token: {}", token);
-                    self.send_user_token_apple_music(token).await?;
-                    self.synchronize_apple_playlists().await?;
-
-                    // Récupérer et émettre les playlists Apple Music
-                    match self.get_apple_music_playlists().await {
-                        Ok(playlists) => {
-                            info!("Apple Music playlists retrieved successfully");
-                            match self.app_handle.emit("apple_music_playlists", playlists) {
-                                Ok(_) => info!("apple_music_playlists event emitted"),
-                                Err(e) => {
-                                    error!("Error emitting apple_music_playlists event: {}", e)
-                                }
-                            };
-                        }
-                        Err(e) => {
-                            error!("Error getting Apple Music playlists: {}", e);
-                        }
-                    }
-                } else {
-                    error!("Apple Music user token is None");
-                }
-            }
-            val if val == "notAuthorized".to_string() => error!("Apple Music not authorized"),
-            _ => error!("Apple Music authorization failed"),
-        }
-        Ok(auth_response)
-    }
-
-    pub async fn send_user_token_apple_music(&self, token: String) -> AppResult<()> {
-        let request = AddTokenRequest { token };
-        self.apple_service.send_authorization_token(request).await?;
-        Ok(())
-    }
-
-    pub async fn get_apple_music_playlists(&self) -> AppResult<GetPlaylistResponse> {
-        let params = GetPlaylistsParams {
-            origin: Some(PlaylistOrigin::AppleMusic),
-            include_musics: true,
-        };
-        let response = self.playlist_service.get_playlists(params).await?;
-        Ok(response)
+    pub async fn create_share_link(
+        &self,
+        playlist_id: i32,
+        req: CreateShareLinkRequest,
+    ) -> AppResult<ShareLink> {
+        self.playlist_service
+            .create_share_link(playlist_id, req)
+            .await
     }
 
-    pub async fn disconnect_spotify(&self) -> AppResult<StatusCode> {
-        self.spotify_client.disconnect().await
+    pub async fn revoke_share_link(&self, token: &str) -> AppResult<StatusCode> {
+        self.playlist_service.revoke_share_link(token).await
     }
 
-    pub async fn disconnect_youtube(&self) -> AppResult<StatusCode> {
-        self.youtube_service.disconnect().await
+    /// Open the share link kept while logged out, once the frontend routes as a logged-in user
+    pub async fn open_pending_share_link(&self) {
+        let token = self.pending_share_link.lock().await.take();
+        if let Some(token) = token {
+            self.open_share_link(&token).await;
+        }
     }
 
-    pub async fn disconnect_apple_music(&self) -> AppResult<StatusCode> {
-        self.apple_service.disconnect().await
+    /// Resolve a playlist share link and take the user to their shared playlists
+    async fn open_share_link(&self, token: &str) {
+        match self.playlist_service.resolve_share_link(token).await {
+            Ok(link) => {
+                info!("Opened share link for playlist {}", link.playlist_id);
+                if let Err(e) = self.app_handle.emit("shared_playlist_opened", link) {
+                    error!("Failed to emit shared_playlist_opened event: {}", e);
+                }
+                if let Err(e) = self.app_handle.emit("routing", "/home/shared") {
+                    error!("Failed to emit routing event: {}", e);
+                }
+            }
+            Err(e) => {
+                error!("Failed to resolve share link: {}", e);
+                let error =
+                    ErrorNotification::server_error("This playlist link is invalid or has expired");
+                let _ = self.app_handle.emit("error_notification", error);
+            }
+        }
     }
 }
@@ -1,43 +1,118 @@
-use crate::backend::backend::BackendClient;
-use crate::error::AppResult;
-use swaptun_backend::{AddTokenRequest, GetDeveloperToken};
+use crate::backend::backend::RequestOptions;
+use crate::backend::oauth::PlatformAuthFailure;
+use crate::backend::platform::{MusicPlatform, PlatformContext, PlatformStatus};
+use crate::backend::vault::CredentialKey;
+use crate::error::{AppError, AppResult};
+use async_trait::async_trait;
+use log::info;
+use swaptun_backend::{AddTokenRequest, GetDeveloperToken, GetPlaylistResponse, PlaylistOrigin};
 use tauri::http::StatusCode;
+use tauri::Url;
+use tauri_plugin_musickit::MusicKitExt;
 
 pub struct AppleService {
-    backend_client: BackendClient,
+    context: PlatformContext,
 }
 
 impl AppleService {
-    pub fn new(app_handle: tauri::AppHandle) -> Self {
-        Self {
-            backend_client: BackendClient::new(app_handle),
-        }
+    pub fn new(context: PlatformContext) -> Self {
+        Self { context }
     }
 
     pub async fn get_developer_token(&self) -> AppResult<GetDeveloperToken> {
-        self.backend_client
+        self.context
+            .backend_client
             .get::<GetDeveloperToken>("apple/developer-token")
             .await
     }
 
-    pub async fn send_authorization_token(&self, request: AddTokenRequest) -> AppResult<()> {
-        let url = "apple/token";
-        self.backend_client
-            .post(url, serde_json::to_string(&request).unwrap())
+    pub async fn send_authorization_token(&self, token: String) -> AppResult<()> {
+        self.context.backend_client.credentials().store(
+            CredentialKey::PlatformToken(PlaylistOrigin::AppleMusic),
+            &token,
+        )?;
+        let request = AddTokenRequest { token };
+        self.context
+            .backend_client
+            .post_json("apple/token", &request)
             .await?;
         Ok(())
     }
+}
+
+#[async_trait]
+impl MusicPlatform for AppleService {
+    fn origin(&self) -> PlaylistOrigin {
+        PlaylistOrigin::AppleMusic
+    }
+
+    /// Authorize through MusicKit on the device, then import the library
+    async fn connect(&self, request_id: Option<String>) -> AppResult<()> {
+        let developer_token = self.get_developer_token().await?;
+        // Keep MusicKit calls together, the plugin handle is not held across awaits
+        let token = {
+            let music_kit = self.context.app_handle.music_kit();
+            music_kit.set_developer_token(developer_token.developer_token)?;
+            let auth_response = music_kit.authorize()?;
+            info!("Apple Music authorization response: {:?}", auth_response);
+            if auth_response.status != "authorized" {
+                return Err(AppError::Auth(
+                    format!("Apple Music not authorized: {}", auth_response.status).into(),
+                ));
+            }
+            music_kit
+                .get_user_token()?
+                .token
+                .ok_or_else(|| AppError::Auth("Apple Music user token is missing".into()))?
+        };
+        info!("Apple Music user token received");
+        self.send_authorization_token(token).await?;
+        self.import_playlists(request_id).await?;
+        self.context
+            .emit_playlists(PlaylistOrigin::AppleMusic)
+            .await;
+        Ok(())
+    }
+
+    /// MusicKit authorizes in-app, no redirect ever comes back
+    async fn handle_callback(&self, _callback: &Url) -> Result<(), PlatformAuthFailure> {
+        Err(PlatformAuthFailure::UnknownFlow)
+    }
+
+    async fn import_playlists(&self, request_id: Option<String>) -> AppResult<StatusCode> {
+        self.context
+            .backend_client
+            .post_json_with_options(
+                "apple/synchronize",
+                &(),
+                RequestOptions::long_running(request_id),
+            )
+            .await
+    }
 
-    pub async fn synchronize_playlists(&self) -> AppResult<StatusCode> {
-        let url = "apple/synchronize";
-        self.backend_client
-            .post(url, serde_json::to_string(&()).unwrap())
+    async fn list_playlists(&self) -> AppResult<GetPlaylistResponse> {
+        self.context
+            .list_playlists(PlaylistOrigin::AppleMusic)
             .await
     }
 
-    pub async fn disconnect(&self) -> AppResult<StatusCode> {
-        self.backend_client
+    async fn disconnect(&self) -> AppResult<StatusCode> {
+        let status = self
+            .context
+            .backend_client
             .delete("apple/disconnect")
+            .await?;
+        self.context
+            .backend_client
+            .credentials()
+            .clear(CredentialKey::PlatformToken(PlaylistOrigin::AppleMusic))?;
+        Ok(status)
+    }
+
+    async fn status(&self) -> AppResult<PlatformStatus> {
+        self.context
+            .backend_client
+            .get_with_options("apple/status", RequestOptions::quiet())
             .await
     }
 }
@@ -1,112 +1,264 @@
-use crate::error::{AppError, AppResult};
+use crate::backend::cancellation::CancellationRegistry;
+use crate::backend::environment::{
+    load_environment, resolve_base_url, BackendEnvironment, EnvironmentConfig,
+    ENVIRONMENT_CHANGED_EVENT,
+};
+use crate::backend::reachability::ReachabilityMonitor;
+use crate::backend::retry::{retry_after, RetryPolicy, IDEMPOTENCY_KEY_HEADER};
+use crate::backend::session::SessionManager;
+use crate::backend::timeouts::{EndpointClass, TimeoutConfig};
+use crate::backend::vault::CredentialVault;
+use crate::error::{AppError, AppResult, ErrorCode};
 use crate::models::ErrorNotification;
 use core::str;
 use log::{debug, error, info, warn};
-use rspotify::model::error;
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::fmt::Debug;
-use tauri::{async_runtime::Mutex, AppHandle, Emitter};
-use tauri_plugin_http::reqwest::{Body, Client, RequestBuilder, Response, StatusCode};
-use tauri_plugin_pinia::ManagerExt;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Listener};
+use tauri_plugin_http::reqwest::{Body, Client, Method, RequestBuilder, Response, StatusCode};
 use tokio::time::sleep;
-const DEFAULT_PORT: &str = "8000";
+use uuid::Uuid;
+
+/// How long a request issued while offline waits for the backend to come back
+const OFFLINE_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// Credentials attached to a single request
+#[derive(Debug, Clone, Default)]
+pub enum AuthContext {
+    /// Use the logged-in user's session token
+    #[default]
+    Session,
+    /// Send no credentials, e.g. for login and registration
+    Anonymous,
+    /// Use an explicit bearer token, e.g. a password reset token
+    Bearer(String),
+}
+
+/// Per-request settings passed down to `send_request`
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+    pub auth: AuthContext,
+    /// Opts a non-idempotent request into automatic retries
+    pub idempotency_key: Option<String>,
+    pub class: EndpointClass,
+    /// Lets the frontend abort the request through `cancel_request`
+    pub request_id: Option<String>,
+    /// Skips the error toast, for probes whose failures are expected and handled by the caller
+    pub quiet: bool,
+}
+
+impl RequestOptions {
+    /// Options for a POST that is safe to replay thanks to a fresh idempotency key
+    pub fn idempotent() -> Self {
+        Self {
+            idempotency_key: Some(Uuid::new_v4().to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Options for a probe that reports its failures itself instead of toasting them
+    pub fn quiet() -> Self {
+        Self {
+            quiet: true,
+            ..Default::default()
+        }
+    }
 
+    /// Options for a long-running call the frontend may cancel
+    pub fn long_running(request_id: Option<String>) -> Self {
+        Self {
+            class: EndpointClass::LongRunning,
+            request_id,
+            ..Default::default()
+        }
+    }
+}
+
+/// HTTP client shared by every service, owning the connection pool
 pub struct BackendClient {
-    client: Client,
-    base_url: String,
+    client: Arc<RwLock<Client>>,
+    base_url: Arc<RwLock<String>>,
     app_handle: AppHandle,
-    next_request_token: Mutex<Option<String>>,
+    reachability: Arc<ReachabilityMonitor>,
+    retry_policy: RetryPolicy,
+    timeouts: TimeoutConfig,
+    cancellations: CancellationRegistry,
+    session: SessionManager,
+    vault: Arc<CredentialVault>,
 }
 
 impl BackendClient {
     pub fn new(app_handle: AppHandle) -> Self {
-        let base_url = Self::determine_base_url(&app_handle);
+        let config = EnvironmentConfig::load(&app_handle);
+        let environment = load_environment(&app_handle, &config);
+        let base_url = Self::determine_base_url(&app_handle, &config, &environment);
         info!("Backend URL initialized: {}", base_url);
 
-        let client = Client::builder()
-            .build()
-            .expect("Failed to build HTTP client");
+        let timeouts = TimeoutConfig::default();
+        let client = Arc::new(RwLock::new(Self::build_client(&timeouts)));
+        let base_url = Arc::new(RwLock::new(base_url));
+
+        Self::listen_environment_changes(
+            &app_handle,
+            client.clone(),
+            base_url.clone(),
+            timeouts.clone(),
+        );
+
+        let reachability = ReachabilityMonitor::new(app_handle.clone());
+        reachability.start(client.clone(), base_url.clone());
+        let vault = Arc::new(CredentialVault::open(&app_handle).unwrap_or_else(|e| {
+            // Without a vault the user has to log in again on every launch, but the app still runs
+            error!(
+                "Credential vault unavailable, keeping credentials in memory: {}",
+                e
+            );
+            CredentialVault::in_memory()
+        }));
+        let session = SessionManager::new(app_handle.clone(), vault.clone());
 
         Self {
             client,
             base_url,
             app_handle,
-            next_request_token: Mutex::new(None),
+            reachability,
+            retry_policy: RetryPolicy::default(),
+            timeouts,
+            cancellations: CancellationRegistry::default(),
+            session,
+            vault,
         }
     }
 
-    /// Determine the backend URL based on build configuration
-    fn determine_base_url(app_handle: &AppHandle) -> String {
-        let host = if cfg!(debug_assertions) {
-            Self::get_dev_host(app_handle)
-        } else {
-            "localhost".to_string()
-        };
-
-        format!("http://{}:{}/api", host, DEFAULT_PORT)
-    }
-
-    /// Get development host based on target platform
-    fn get_dev_host(app_handle: &AppHandle) -> String {
-        if cfg!(target_os = "ios") {
-            "192.168.50.54".to_string()
-        } else if cfg!(target_os = "android") {
-            app_handle
-                .config()
-                .build
-                .dev_url
-                .as_ref()
-                .and_then(|url| url.host_str().map(|s| s.to_string()))
-                .unwrap_or_else(|| "localhost".to_string())
-        } else {
-            "localhost".to_string()
-        }
+    fn build_client(timeouts: &TimeoutConfig) -> Client {
+        Client::builder()
+            .connect_timeout(timeouts.connect)
+            .read_timeout(timeouts.read)
+            .build()
+            .expect("Failed to build HTTP client")
     }
 
-    /// Check internet connectivity with retries
-    async fn check_connectivity(&self) -> AppResult<()> {
-        let max_retries = 30;
-        let mut retry_count = 0;
+    /// Determine the backend URL for the selected environment
+    fn determine_base_url(
+        app_handle: &AppHandle,
+        config: &EnvironmentConfig,
+        environment: &BackendEnvironment,
+    ) -> String {
+        resolve_base_url(app_handle, config, environment).unwrap_or_else(|e| {
+            error!("Invalid backend environment {:?}: {}", environment, e);
+            resolve_base_url(app_handle, config, &BackendEnvironment::Dev)
+                .expect("Dev environment URL must always resolve")
+        })
+    }
 
-        loop {
-            match self.client.get("https://8.8.8.8").send().await {
-                Ok(_) => {
-                    if retry_count > 0 {
-                        info!("Internet connection restored after {} retries", retry_count);
-                    }
-                    return Ok(());
-                }
+    /// Rebuild the HTTP client and base URL whenever the environment changes
+    fn listen_environment_changes(
+        app_handle: &AppHandle,
+        client: Arc<RwLock<Client>>,
+        base_url: Arc<RwLock<String>>,
+        timeouts: TimeoutConfig,
+    ) {
+        let handle = app_handle.clone();
+        app_handle.listen(ENVIRONMENT_CHANGED_EVENT, move |event| {
+            let environment = match serde_json::from_str::<BackendEnvironment>(event.payload()) {
+                Ok(environment) => environment,
                 Err(e) => {
-                    warn!(
-                        "No internet connection (attempt {}): {}",
-                        retry_count + 1,
-                        e
-                    );
-                    if retry_count == 0 {
-                        let error = ErrorNotification::network_error("No internet connection");
-                        let _ = self.app_handle.emit("error_notification", error);
-                    }
+                    error!("Invalid environment_changed payload: {}", e);
+                    return;
+                }
+            };
+            let config = EnvironmentConfig::load(&handle);
+            let url = Self::determine_base_url(&handle, &config, &environment);
+            info!("Backend URL changed: {}", url);
+
+            *base_url.write().unwrap() = url;
+            *client.write().unwrap() = Self::build_client(&timeouts);
+        });
+    }
 
-                    retry_count += 1;
-                    if retry_count >= max_retries {
-                        return Err(AppError::Network(
-                            "No internet connection after maximum retries".to_string(),
-                        ));
-                    }
+    fn client(&self) -> Client {
+        self.client.read().unwrap().clone()
+    }
 
-                    sleep(std::time::Duration::from_secs(2)).await;
-                }
-            }
+    fn base_url(&self) -> String {
+        self.base_url.read().unwrap().clone()
+    }
+
+    fn url(&self, endpoint: &str) -> String {
+        format!("{}/{}", self.base_url(), endpoint)
+    }
+
+    /// Encrypted store for platform tokens
+    pub fn credentials(&self) -> &CredentialVault {
+        &self.vault
+    }
+
+    /// Keep the session token returned by a login, out of reach of the webview
+    pub fn start_session(&self, token: &str) -> AppResult<()> {
+        self.session.start(token)
+    }
+
+    /// Whether a user is logged in on this device
+    pub fn has_session(&self) -> bool {
+        self.session.has_session()
+    }
+
+    /// Abort in-flight requests and wipe every stored credential
+    pub fn clear_credentials(&self) -> AppResult<()> {
+        self.cancellations.cancel_all();
+        self.vault.clear_all()
+    }
+
+    /// Wipe every stored credential without the backend and ask the user to log in again
+    pub fn expire_session(&self, reason: &str) {
+        if let Err(e) = self.clear_credentials() {
+            error!("Failed to clear credentials: {}", e);
+        }
+        self.session.expire(reason.to_string());
+    }
+
+    pub fn is_online(&self) -> bool {
+        self.reachability.is_online()
+    }
+
+    /// Abort an in-flight request started with a `request_id`
+    pub fn cancel(&self, request_id: &str) -> bool {
+        self.cancellations.cancel(request_id)
+    }
+
+    /// Fail fast when the backend is known to be offline, after a short grace period
+    async fn ensure_reachable(&self) -> AppResult<()> {
+        if self.reachability.is_online()
+            || self
+                .reachability
+                .wait_until_online(OFFLINE_GRACE_PERIOD)
+                .await
+        {
+            Ok(())
+        } else {
+            Err(AppError::Network("Backend unreachable".into()).with_code(ErrorCode::Offline))
         }
     }
 
     /// Generic GET request
     pub async fn get<T: DeserializeOwned>(&self, endpoint: &str) -> AppResult<T> {
-        let url = format!("{}/{}", self.base_url, endpoint);
+        self.get_with_options(endpoint, RequestOptions::default())
+            .await
+    }
+
+    pub async fn get_with_options<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        options: RequestOptions,
+    ) -> AppResult<T> {
+        let url = self.url(endpoint);
         debug!("GET {}", url);
 
-        let request = self.client.get(&url);
-        let response = self.send_request(request).await?;
+        let request = self.client().get(&url);
+        let response = self.send_request(request, options).await?;
 
         self.handle_response(response).await
     }
@@ -116,74 +268,338 @@ impl BackendClient {
     where
         U: Into<Body> + Debug,
     {
-        let response = self.post_internal(endpoint, body).await?;
-        Ok(response.status())
-    }
-
-    /// Generic POST request with response body
-    pub async fn post_with_return<T, U>(&self, endpoint: &str, body: U) -> AppResult<T>
-    where
-        U: Into<Body> + Debug,
-        T: DeserializeOwned + Debug,
-    {
-        let response = self.post_internal(endpoint, body).await?;
-        self.handle_response(response).await
+        self.post_with_options(endpoint, body, RequestOptions::default())
+            .await
     }
 
-    /// Internal POST implementation
-    async fn post_internal<U>(&self, endpoint: &str, body: U) -> AppResult<Response>
+    /// POST request returning status code, with explicit request options
+    pub async fn post_with_options<U>(
+        &self,
+        endpoint: &str,
+        body: U,
+        options: RequestOptions,
+    ) -> AppResult<StatusCode>
     where
         U: Into<Body> + Debug,
     {
-        let url = format!("{}/{}", self.base_url, endpoint);
+        let url = self.url(endpoint);
         debug!("POST {} with body: {:?}", url, body);
 
         let request = self
-            .client
+            .client()
             .post(&url)
             .header("Content-Type", "application/json")
             .body(body);
 
-        self.send_request(request).await
+        let response = self.send_request(request, options).await?;
+        Ok(response.status())
     }
 
-    /// GET request with body (non-standard but used in some APIs)
-    pub async fn get_with_body<T, U>(&self, endpoint: &str, body: U) -> AppResult<T>
+    /// POST request with a JSON body, returning status code
+    pub async fn post_json<B>(&self, endpoint: &str, body: &B) -> AppResult<StatusCode>
+    where
+        B: Serialize + ?Sized,
+    {
+        self.post_json_with_options(endpoint, body, RequestOptions::default())
+            .await
+    }
+
+    /// POST request with a JSON body and explicit request options, returning status code
+    pub async fn post_json_with_options<B>(
+        &self,
+        endpoint: &str,
+        body: &B,
+        options: RequestOptions,
+    ) -> AppResult<StatusCode>
+    where
+        B: Serialize + ?Sized,
+    {
+        let response = self
+            .json_request(Method::POST, endpoint, Some(body), options)
+            .await?;
+        Ok(response.status())
+    }
+
+    /// POST request with a JSON body and response body
+    pub async fn post_json_with_return<T, B>(&self, endpoint: &str, body: &B) -> AppResult<T>
     where
         T: DeserializeOwned,
-        U: Into<Body> + Debug,
+        B: Serialize + ?Sized,
     {
-        let url = format!("{}/{}", self.base_url, endpoint);
-        debug!("GET {} with body: {:?}", url, body);
+        self.post_json_with_return_options(endpoint, body, RequestOptions::default())
+            .await
+    }
 
-        let request = self
-            .client
-            .get(&url)
-            .header("Content-Type", "application/json")
-            .body(body);
+    /// POST request with a JSON body, response body and explicit request options
+    pub async fn post_json_with_return_options<T, B>(
+        &self,
+        endpoint: &str,
+        body: &B,
+        options: RequestOptions,
+    ) -> AppResult<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let response = self
+            .json_request(Method::POST, endpoint, Some(body), options)
+            .await?;
+        self.handle_response(response).await
+    }
 
-        let response = self.send_request(request).await?;
+    /// PUT request with a JSON body and response body
+    #[allow(dead_code)]
+    pub async fn put<T, B>(&self, endpoint: &str, body: &B) -> AppResult<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let response = self
+            .json_request(Method::PUT, endpoint, Some(body), RequestOptions::default())
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// PATCH request with a JSON body and response body
+    #[allow(dead_code)]
+    pub async fn patch<T, B>(&self, endpoint: &str, body: &B) -> AppResult<T>
+    where
+        T: DeserializeOwned,
+        B: Serialize + ?Sized,
+    {
+        let response = self
+            .json_request(
+                Method::PATCH,
+                endpoint,
+                Some(body),
+                RequestOptions::default(),
+            )
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// GET request with parameters encoded in the query string
+    pub async fn get_with_query<T, P>(&self, endpoint: &str, params: &P) -> AppResult<T>
+    where
+        T: DeserializeOwned,
+        P: Serialize,
+    {
+        self.get_with_query_options(endpoint, params, RequestOptions::default())
+            .await
+    }
+
+    pub async fn get_with_query_options<T, P>(
+        &self,
+        endpoint: &str,
+        params: &P,
+        options: RequestOptions,
+    ) -> AppResult<T>
+    where
+        T: DeserializeOwned,
+        P: Serialize,
+    {
+        let url = self.url(endpoint);
+        debug!("GET {} with query parameters", url);
+
+        let request = self.client().get(&url).query(params);
+
+        let response = self.send_request(request, options).await?;
         self.handle_response(response).await
     }
 
     /// Generic DELETE request returning status code
     pub async fn delete(&self, endpoint: &str) -> AppResult<StatusCode> {
-        let url = format!("{}/{}", self.base_url, endpoint);
-        debug!("DELETE {}", url);
+        let response = self
+            .json_request::<()>(Method::DELETE, endpoint, None, RequestOptions::default())
+            .await?;
+        Ok(response.status())
+    }
 
-        let request = self.client.delete(&url);
-        let response = self.send_request(request).await?;
+    /// DELETE request with response body
+    #[allow(dead_code)]
+    pub async fn delete_with_return<T: DeserializeOwned>(&self, endpoint: &str) -> AppResult<T> {
+        let response = self
+            .json_request::<()>(Method::DELETE, endpoint, None, RequestOptions::default())
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// DELETE request with a JSON body, returning status code
+    pub async fn delete_with_body<B>(&self, endpoint: &str, body: &B) -> AppResult<StatusCode>
+    where
+        B: Serialize + ?Sized,
+    {
+        let response = self
+            .json_request(
+                Method::DELETE,
+                endpoint,
+                Some(body),
+                RequestOptions::default(),
+            )
+            .await?;
         Ok(response.status())
     }
 
-    /// Send request with authentication and error handling
-    async fn send_request(&self, request: RequestBuilder) -> AppResult<Response> {
-        self.check_connectivity().await?;
+    /// Build and send a request with an optional JSON body
+    async fn json_request<B>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: Option<&B>,
+        options: RequestOptions,
+    ) -> AppResult<Response>
+    where
+        B: Serialize + ?Sized,
+    {
+        let url = self.url(endpoint);
+        let mut request = self.client().request(method.clone(), &url);
+
+        match body {
+            Some(body) => {
+                let body = serde_json::to_string(body)?;
+                debug!("{} {} with body: {}", method, url, body);
+                request = request
+                    .header("Content-Type", "application/json")
+                    .body(body);
+            }
+            None => debug!("{} {}", method, url),
+        }
+
+        self.send_request(request, options).await
+    }
 
-        let request = self.add_authorization_header(request).await;
+    /// Send request, abortable through `cancel` when it carries a request id
+    async fn send_request(
+        &self,
+        request: RequestBuilder,
+        options: RequestOptions,
+    ) -> AppResult<Response> {
+        let Some(request_id) = options.request_id.clone() else {
+            return self.send_authenticated(request, options).await;
+        };
 
-        match request.send().await {
+        let guard = self.cancellations.register(request_id.clone());
+        tokio::select! {
+            result = self.send_authenticated(request, options) => result,
+            _ = guard.token.cancelled() => {
+                info!("Request {} cancelled", request_id);
+                Err(AppError::Network("Request cancelled".into()).with_code(ErrorCode::Cancelled))
+            }
+        }
+    }
+
+    /// Send request, refreshing the session and replaying it once after a 401
+    async fn send_authenticated(
+        &self,
+        request: RequestBuilder,
+        options: RequestOptions,
+    ) -> AppResult<Response> {
+        let (replay, sent_token) = match options.auth {
+            AuthContext::Session => (request.try_clone(), self.session_token().await),
+            _ => (None, None),
+        };
+
+        let result = self
+            .send_with_retries(request, options.clone(), sent_token.clone())
+            .await;
+
+        match (result, replay, sent_token) {
+            (Err(e), Some(replay), Some(stale_token)) if e.status() == Some(401) => {
+                info!("Request unauthorized, refreshing session and retrying once");
+                let token = self
+                    .session
+                    .refresh(&self.client(), &self.base_url(), &stale_token)
+                    .await?;
+                self.send_with_retries(replay, options, Some(token)).await
+            }
+            (result, _, _) => result,
+        }
+    }
+
+    /// Session token for the next request, refreshed ahead of its expiry
+    async fn session_token(&self) -> Option<String> {
+        self.session
+            .access_token(&self.client(), &self.base_url())
+            .await
+    }
+
+    /// Send request with authentication, timeouts, retries and error handling
+    async fn send_with_retries(
+        &self,
+        request: RequestBuilder,
+        options: RequestOptions,
+        session_token: Option<String>,
+    ) -> AppResult<Response> {
+        self.ensure_reachable().await?;
+
+        let mut request = self
+            .add_authorization_header(request, options.auth, session_token)
+            .timeout(self.timeouts.total(options.class));
+        if let Some(key) = &options.idempotency_key {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+
+        let (client, request) = request.build_split();
+        let request = request?;
+        let retryable =
+            RetryPolicy::is_idempotent(request.method()) || options.idempotency_key.is_some();
+
+        let mut attempt = 1;
+        loop {
+            let can_retry = retryable && attempt < self.retry_policy.max_attempts;
+            let attempt_request = match request.try_clone() {
+                Some(attempt_request) => attempt_request,
+                None => {
+                    return self
+                        .check_response(client.execute(request).await, options.quiet)
+                        .await
+                }
+            };
+
+            let result = client.execute(attempt_request).await;
+            let retry_delay = match &result {
+                Ok(response) if RetryPolicy::is_retryable_status(response.status()) => {
+                    Some(self.retry_policy.delay(attempt, retry_after(response)))
+                }
+                Err(e) if e.is_connect() || e.is_timeout() => {
+                    Some(self.retry_policy.delay(attempt, None))
+                }
+                _ => None,
+            };
+
+            match retry_delay {
+                Some(delay) if can_retry => {
+                    warn!(
+                        "{} {} failed (attempt {}/{}), retrying in {:?}",
+                        request.method(),
+                        request.url(),
+                        attempt,
+                        self.retry_policy.max_attempts,
+                        delay
+                    );
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                _ => return self.check_response(result, options.quiet).await,
+            }
+        }
+    }
+
+    /// Turn the outcome of a request into a successful response or an AppError
+    async fn check_response(
+        &self,
+        result: Result<Response, tauri_plugin_http::reqwest::Error>,
+        quiet: bool,
+    ) -> AppResult<Response> {
+        if let Err(e) = &result {
+            if e.is_connect() || e.is_timeout() {
+                self.reachability.report(false);
+            }
+        }
+
+        match result {
             Ok(response) => {
+                self.reachability.report(true);
                 if response.status().is_success() {
                     Ok(response)
                 } else {
@@ -191,30 +607,16 @@ impl BackendClient {
                     let error_text = response.text().await.unwrap_or_default();
                     error!("Request failed with status {}: {}", status, error_text);
 
-                    let error_msg = if error_text.is_empty() {
-                        format!("Server error: {}", status)
-                    } else {
-                        error_text.clone()
-                    };
-                    let error = ErrorNotification::server_error("Server error");
-                    let _ = self.app_handle.emit("error_notification", error);
-
-                    if status.is_client_error() {
-                        Err(AppError::Backend(error_text))
-                    } else {
-                        Err(AppError::Internal(error_text))
+                    // Expired sessions are reported through `session_expired` instead
+                    if status != StatusCode::UNAUTHORIZED && !quiet {
+                        let error = ErrorNotification::server_error("Server error");
+                        let _ = self.app_handle.emit("error_notification", error);
                     }
+
+                    Err(AppError::from_response(status, error_text))
                 }
             }
-            Err(e) => {
-                if e.is_timeout() {
-                    Err(AppError::Network("Request timeout".to_string()))
-                } else if e.is_connect() {
-                    Err(AppError::Network("Connection refused".to_string()))
-                } else {
-                    Err(AppError::Network(e.to_string()))
-                }
-            }
+            Err(e) => Err(e.into()),
         }
     }
 
@@ -223,30 +625,26 @@ impl BackendClient {
         response
             .json::<T>()
             .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse response: {}", e)))
+            .map_err(|e| AppError::Internal(format!("Failed to parse response: {}", e).into()))
     }
 
     /// Add authorization header to request
-    async fn add_authorization_header(&self, request: RequestBuilder) -> RequestBuilder {
-        // Try temporary token first, then pinia token
-        let mut temp_token = self.next_request_token.lock().await;
-
-        if let Some(token) = temp_token.take() {
-            debug!("Using temporary auth token");
-            return request.header("Authorization", format!("Bearer {}", token));
-        }
-
-        if let Some(token) = self.app_handle.pinia().get("user", "token") {
-            let string_token = token.as_str().unwrap_or_default();
-            return request.header("Authorization", format!("Bearer {}", string_token));
+    fn add_authorization_header(
+        &self,
+        request: RequestBuilder,
+        auth: AuthContext,
+        session_token: Option<String>,
+    ) -> RequestBuilder {
+        match auth {
+            AuthContext::Bearer(token) => {
+                debug!("Using explicit auth token");
+                request.header("Authorization", format!("Bearer {}", token))
+            }
+            AuthContext::Session => match session_token {
+                Some(token) => request.header("Authorization", format!("Bearer {}", token)),
+                None => request,
+            },
+            AuthContext::Anonymous => request,
         }
-
-        request
-    }
-
-    /// Set a temporary token for the next request
-    pub async fn set_temporary_token(&self, token: String) {
-        let mut temp_token = self.next_request_token.lock().await;
-        *temp_token = Some(token);
     }
 }
@@ -0,0 +1,75 @@
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+/// In-flight requests the frontend can abort by id
+#[derive(Default)]
+pub struct CancellationRegistry {
+    tokens: Mutex<HashMap<String, (u64, CancellationToken)>>,
+    next_generation: AtomicU64,
+}
+
+impl CancellationRegistry {
+    /// Track a request until the returned guard is dropped
+    pub fn register(&self, request_id: String) -> CancellationGuard<'_> {
+        let token = CancellationToken::new();
+        let generation = self.next_generation.fetch_add(1, Ordering::Relaxed);
+        let previous = self
+            .tokens
+            .lock()
+            .unwrap()
+            .insert(request_id.clone(), (generation, token.clone()));
+        if previous.is_some() {
+            warn!("Request id {} reused while still in flight", request_id);
+        }
+
+        CancellationGuard {
+            registry: self,
+            request_id,
+            generation,
+            token,
+        }
+    }
+
+    /// Cancel every request in flight, e.g. on logout
+    pub fn cancel_all(&self) {
+        for (request_id, (_, token)) in self.tokens.lock().unwrap().drain() {
+            debug!("Cancelling request {}", request_id);
+            token.cancel();
+        }
+    }
+
+    /// Cancel a request, returning false if it is not in flight
+    pub fn cancel(&self, request_id: &str) -> bool {
+        match self.tokens.lock().unwrap().remove(request_id) {
+            Some((_, token)) => {
+                debug!("Cancelling request {}", request_id);
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+pub struct CancellationGuard<'a> {
+    registry: &'a CancellationRegistry,
+    request_id: String,
+    generation: u64,
+    pub token: CancellationToken,
+}
+
+impl Drop for CancellationGuard<'_> {
+    fn drop(&mut self) {
+        let mut tokens = self.registry.tokens.lock().unwrap();
+        // Only forget our own entry, a newer request may have reused the id
+        if tokens
+            .get(&self.request_id)
+            .is_some_and(|(generation, _)| *generation == self.generation)
+        {
+            tokens.remove(&self.request_id);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::error::{AppError, AppResult};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Url};
+use tauri_plugin_pinia::ManagerExt;
+
+const DEFAULT_PORT: &str = "8000";
+const SETTINGS_STORE: &str = "settings";
+const ENVIRONMENT_KEY: &str = "backend_environment";
+
+/// Event emitted after the backend environment has been switched
+pub const ENVIRONMENT_CHANGED_EVENT: &str = "environment_changed";
+
+/// Named backend environment the app can talk to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "name", content = "url", rename_all = "snake_case")]
+pub enum BackendEnvironment {
+    Dev,
+    Staging,
+    Prod,
+    Custom(String),
+}
+
+impl Default for BackendEnvironment {
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            BackendEnvironment::Dev
+        } else {
+            BackendEnvironment::Prod
+        }
+    }
+}
+
+/// Environment URLs read from the `plugins.backend` section of `tauri.conf.json`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentConfig {
+    pub dev: Option<String>,
+    pub staging: Option<String>,
+    pub prod: Option<String>,
+    pub default: Option<BackendEnvironment>,
+}
+
+impl EnvironmentConfig {
+    pub fn load(app_handle: &AppHandle) -> Self {
+        match app_handle.config().plugins.0.get("backend") {
+            Some(value) => serde_json::from_value(value.clone()).unwrap_or_else(|e| {
+                warn!("Invalid backend configuration, using defaults: {}", e);
+                Self::default()
+            }),
+            None => Self::default(),
+        }
+    }
+}
+
+/// Resolve the base URL of an environment, enforcing HTTPS outside of dev
+pub fn resolve_base_url(
+    app_handle: &AppHandle,
+    config: &EnvironmentConfig,
+    environment: &BackendEnvironment,
+) -> AppResult<String> {
+    let url = match environment {
+        BackendEnvironment::Dev => config
+            .dev
+            .clone()
+            .unwrap_or_else(|| format!("http://{}:{}/api", get_dev_host(app_handle), DEFAULT_PORT)),
+        BackendEnvironment::Staging => config.staging.clone().ok_or_else(|| {
+            AppError::Validation("No URL configured for the staging environment".into())
+        })?,
+        BackendEnvironment::Prod => config.prod.clone().ok_or_else(|| {
+            AppError::Validation("No URL configured for the prod environment".into())
+        })?,
+        BackendEnvironment::Custom(url) => url.clone(),
+    };
+
+    let parsed = Url::parse(&url)
+        .map_err(|e| AppError::Validation(format!("Invalid backend URL {}: {}", url, e).into()))?;
+
+    if *environment != BackendEnvironment::Dev && parsed.scheme() != "https" {
+        return Err(AppError::Validation(
+            format!("Backend URL must use HTTPS outside of dev: {}", url).into(),
+        ));
+    }
+
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+/// Get development host based on target platform
+fn get_dev_host(app_handle: &AppHandle) -> String {
+    if cfg!(target_os = "android") || cfg!(target_os = "ios") {
+        app_handle
+            .config()
+            .build
+            .dev_url
+            .as_ref()
+            .and_then(|url| url.host_str().map(|s| s.to_string()))
+            .unwrap_or_else(|| "localhost".to_string())
+    } else {
+        "localhost".to_string()
+    }
+}
+
+/// Environment selected at runtime, falling back to the configured default
+pub fn load_environment(app_handle: &AppHandle, config: &EnvironmentConfig) -> BackendEnvironment {
+    app_handle
+        .pinia()
+        .get(SETTINGS_STORE, ENVIRONMENT_KEY)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .or_else(|| config.default.clone())
+        .unwrap_or_default()
+}
+
+/// Persist the selected environment so it survives restarts
+pub fn save_environment(app_handle: &AppHandle, environment: &BackendEnvironment) -> AppResult<()> {
+    let value = serde_json::to_value(environment)?;
+    app_handle
+        .pinia()
+        .set(SETTINGS_STORE, ENVIRONMENT_KEY, value)
+        .map_err(|e| AppError::Internal(format!("Failed to persist environment: {}", e).into()))?;
+    info!("Backend environment saved: {:?}", environment);
+    Ok(())
+}
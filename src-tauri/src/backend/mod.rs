@@ -1,16 +1,30 @@
 mod apple;
 pub mod backend;
-pub mod deezer;
+pub mod cancellation;
+pub mod environment;
 pub mod notification;
+pub mod oauth;
+pub mod oauth_platform;
+pub mod platform;
 pub mod playlist;
+pub mod reachability;
+pub mod retry;
+pub mod session;
 pub mod spotify;
+pub mod timeouts;
 pub mod user;
+pub mod vault;
 pub mod youtube;
 
 pub use apple::*;
-pub use deezer::*;
+pub use environment::{BackendEnvironment, ENVIRONMENT_CHANGED_EVENT};
 pub use notification::*;
+pub use oauth::*;
+pub use oauth_platform::*;
+pub use platform::*;
 pub use playlist::*;
+pub use reachability::ConnectivityStatus;
 pub use spotify::*;
 pub use user::*;
+pub use vault::CredentialKey;
 pub use youtube::*;
@@ -1,36 +1,66 @@
 use log::info;
-use serde_json;
-use tauri::AppHandle;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
 use tauri_plugin_http::reqwest::StatusCode;
 
 use crate::backend::backend::BackendClient;
 use crate::error::AppResult;
 use swaptun_backend::{RegisterFcmTokenRequest, SendTestNotificationRequest};
 
+#[derive(Debug, Serialize)]
+struct UnregisterFcmTokenRequest {
+    token: String,
+}
+
 pub struct NotificationService {
-    backend_client: BackendClient,
+    backend_client: Arc<BackendClient>,
+    /// FCM token registered for this device, unregistered on logout
+    fcm_token: Mutex<Option<String>>,
 }
 
 impl NotificationService {
-    pub fn new(app_handle: AppHandle) -> Self {
+    pub fn new(backend_client: Arc<BackendClient>) -> Self {
         Self {
-            backend_client: BackendClient::new(app_handle),
+            backend_client,
+            fcm_token: Mutex::new(None),
         }
     }
 
-    pub async fn set_fcm_token(&self, register_fcm_token_request: RegisterFcmTokenRequest) -> AppResult<StatusCode> {
+    pub async fn set_fcm_token(
+        &self,
+        register_fcm_token_request: RegisterFcmTokenRequest,
+    ) -> AppResult<StatusCode> {
         info!("Setting FCM token");
-        let body = serde_json::to_string(&register_fcm_token_request)?;
+        let status = self
+            .backend_client
+            .post_json("notifications/fcm-token", &register_fcm_token_request)
+            .await?;
+        *self.fcm_token.lock().unwrap() = Some(register_fcm_token_request.token);
+        Ok(status)
+    }
+
+    /// Detach this device's FCM token from the account so it stops receiving pushes
+    pub async fn unregister_fcm_token(&self) -> AppResult<()> {
+        let Some(token) = self.fcm_token.lock().unwrap().take() else {
+            return Ok(());
+        };
+        info!("Unregistering FCM token");
         self.backend_client
-            .post("notifications/fcm-token", body)
-            .await
+            .delete_with_body(
+                "notifications/fcm-token",
+                &UnregisterFcmTokenRequest { token },
+            )
+            .await?;
+        Ok(())
     }
 
-    pub async fn send_test_notification(&self, notification_request: SendTestNotificationRequest) -> AppResult<StatusCode> {
+    pub async fn send_test_notification(
+        &self,
+        notification_request: SendTestNotificationRequest,
+    ) -> AppResult<StatusCode> {
         info!("Sending test notification");
-        let body = serde_json::to_string(&notification_request)?;
         self.backend_client
-            .post("notifications/test-notification", body)
+            .post_json("notifications/test-notification", &notification_request)
             .await
     }
 }
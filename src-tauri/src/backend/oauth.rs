@@ -0,0 +1,193 @@
+use crate::backend::backend::BackendClient;
+use crate::backend::vault::CredentialKey;
+use crate::error::{AppError, AppResult};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use log::{info, warn};
+use rand::distr::{Alphanumeric, SampleString};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use swaptun_backend::PlaylistOrigin;
+use tauri::Url;
+
+/// Event emitted when a platform authorization callback is rejected or fails
+pub const PLATFORM_AUTH_FAILED_EVENT: &str = "platform_auth_failed";
+
+/// How long the user has to complete the platform consent screen
+const STATE_TTL: Duration = Duration::from_secs(10 * 60);
+const STATE_LEN: usize = 32;
+const CODE_VERIFIER_LEN: usize = 64;
+
+/// Why a platform authorization did not go through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlatformAuthFailure {
+    /// The user refused access on the consent screen
+    Denied,
+    /// No authorization was started from this app
+    UnknownFlow,
+    MissingState,
+    StateMismatch,
+    StateExpired,
+    MissingCode,
+    /// The backend could not exchange the code for a token
+    ExchangeFailed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformAuthFailed {
+    pub platform: PlaylistOrigin,
+    pub reason: PlatformAuthFailure,
+}
+
+/// Token exchange request, with the PKCE verifier when the flow used one
+#[derive(Debug, Serialize)]
+pub struct PlatformTokenRequest {
+    pub token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_verifier: Option<String>,
+}
+
+/// Validated callback of a platform authorization
+#[derive(Debug)]
+pub struct OAuthCallback {
+    pub code: String,
+    pub code_verifier: Option<String>,
+}
+
+impl OAuthCallback {
+    pub fn into_token_request(self) -> PlatformTokenRequest {
+        PlatformTokenRequest {
+            token: self.code,
+            code_verifier: self.code_verifier,
+        }
+    }
+}
+
+/// Authorization started by the app, kept in the vault until its callback arrives
+#[derive(Debug, Serialize, Deserialize)]
+struct PendingAuthorization {
+    state: String,
+    code_verifier: Option<String>,
+    started_at: u64,
+}
+
+/// Binds platform callbacks to the authorization the app started
+pub struct OAuthStates {
+    backend_client: Arc<BackendClient>,
+}
+
+impl OAuthStates {
+    pub fn new(backend_client: Arc<BackendClient>) -> Self {
+        Self { backend_client }
+    }
+
+    fn supports_pkce(platform: &PlaylistOrigin) -> bool {
+        matches!(
+            platform,
+            PlaylistOrigin::Spotify
+                | PlaylistOrigin::YoutubeMusic
+                | PlaylistOrigin::Tidal
+                | PlaylistOrigin::SoundCloud
+                | PlaylistOrigin::AmazonMusic
+        )
+    }
+
+    /// Add a fresh `state` (and PKCE challenge) to an authorization URL and remember them
+    pub fn begin(&self, platform: PlaylistOrigin, auth_url: &str) -> AppResult<String> {
+        let mut url = Url::parse(auth_url).map_err(|e| {
+            AppError::Validation(format!("Invalid authorization URL: {}", e).into())
+        })?;
+
+        let state = Alphanumeric.sample_string(&mut rand::rng(), STATE_LEN);
+        let code_verifier = Self::supports_pkce(&platform)
+            .then(|| Alphanumeric.sample_string(&mut rand::rng(), CODE_VERIFIER_LEN));
+
+        // Drop any state or challenge the backend put in, ours are the ones we check
+        let params: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| {
+                !matches!(
+                    key.as_ref(),
+                    "state" | "code_challenge" | "code_challenge_method"
+                )
+            })
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .clear()
+                .extend_pairs(params)
+                .append_pair("state", &state);
+            if let Some(verifier) = &code_verifier {
+                let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+                query
+                    .append_pair("code_challenge", &challenge)
+                    .append_pair("code_challenge_method", "S256");
+            }
+        }
+
+        let pending = PendingAuthorization {
+            state,
+            code_verifier,
+            started_at: now_secs(),
+        };
+        self.backend_client.credentials().store(
+            CredentialKey::OAuthState(platform),
+            &serde_json::to_string(&pending)?,
+        )?;
+        Ok(url.into())
+    }
+
+    /// Check a callback against the pending authorization, which is consumed either way
+    pub fn complete(
+        &self,
+        platform: PlaylistOrigin,
+        callback: &Url,
+    ) -> Result<OAuthCallback, PlatformAuthFailure> {
+        let vault = self.backend_client.credentials();
+        let pending = vault
+            .load(CredentialKey::OAuthState(platform.clone()))
+            .and_then(|value| serde_json::from_str::<PendingAuthorization>(&value).ok());
+        if let Err(e) = vault.clear(CredentialKey::OAuthState(platform)) {
+            warn!("Failed to clear OAuth state: {}", e);
+        }
+
+        let param = |name: &str| {
+            callback
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+
+        // Deezer reports a refusal as `error_reason` rather than `error`
+        if param("error").is_some() || param("error_reason").is_some() {
+            return Err(PlatformAuthFailure::Denied);
+        }
+        let pending = pending.ok_or(PlatformAuthFailure::UnknownFlow)?;
+        let state = param("state").ok_or(PlatformAuthFailure::MissingState)?;
+        if state != pending.state {
+            return Err(PlatformAuthFailure::StateMismatch);
+        }
+        if now_secs().saturating_sub(pending.started_at) > STATE_TTL.as_secs() {
+            return Err(PlatformAuthFailure::StateExpired);
+        }
+        let code = param("code").ok_or(PlatformAuthFailure::MissingCode)?;
+
+        info!("OAuth callback state validated");
+        Ok(OAuthCallback {
+            code,
+            code_verifier: pending.code_verifier,
+        })
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
@@ -0,0 +1,102 @@
+use crate::backend::backend::RequestOptions;
+use crate::backend::oauth::PlatformAuthFailure;
+use crate::backend::platform::{
+    AuthorizationUrlResponse, MusicPlatform, PlatformContext, PlatformStatus,
+};
+use crate::error::AppResult;
+use async_trait::async_trait;
+use log::{error, info};
+use swaptun_backend::{GetPlaylistResponse, PlaylistOrigin};
+use tauri::http::StatusCode;
+use tauri::Url;
+
+/// A platform linked through the backend's OAuth flow, whose routes all live under `slug`
+pub struct OAuthPlatformClient {
+    context: PlatformContext,
+    origin: PlaylistOrigin,
+    slug: &'static str,
+}
+
+impl OAuthPlatformClient {
+    pub fn new(context: PlatformContext, origin: PlaylistOrigin, slug: &'static str) -> Self {
+        Self {
+            context,
+            origin,
+            slug,
+        }
+    }
+
+    /// Request id of the playlist import triggered by the callback, cancellable from the UI
+    pub fn import_request_id(&self) -> String {
+        format!("{}_import", self.slug)
+    }
+
+    fn route(&self, path: &str) -> String {
+        format!("{}/{}", self.slug, path)
+    }
+
+    pub async fn get_auth_url(&self) -> AppResult<AuthorizationUrlResponse> {
+        self.context
+            .backend_client
+            .get::<AuthorizationUrlResponse>(&self.route("authorization-url"))
+            .await
+    }
+}
+
+#[async_trait]
+impl MusicPlatform for OAuthPlatformClient {
+    fn origin(&self) -> PlaylistOrigin {
+        self.origin.clone()
+    }
+
+    async fn connect(&self, _request_id: Option<String>) -> AppResult<()> {
+        let response = self.get_auth_url().await?;
+        self.context
+            .open_authorization(self.origin.clone(), &response.url)
+    }
+
+    /// Exchange the code, then import the user's playlists right away
+    async fn handle_callback(&self, callback: &Url) -> Result<(), PlatformAuthFailure> {
+        self.context
+            .exchange_code(self.origin.clone(), callback, &self.route("token"))
+            .await?;
+        match self.import_playlists(Some(self.import_request_id())).await {
+            Ok(status) => info!(
+                "{:?} playlists imported with status: {:?}",
+                self.origin, status
+            ),
+            Err(e) => error!("Error importing {:?} playlists: {}", self.origin, e),
+        }
+        self.context.emit_playlists(self.origin.clone()).await;
+        Ok(())
+    }
+
+    async fn import_playlists(&self, request_id: Option<String>) -> AppResult<StatusCode> {
+        self.context
+            .backend_client
+            .post_json_with_options(
+                &self.route("playlist"),
+                &(),
+                RequestOptions::long_running(request_id),
+            )
+            .await
+    }
+
+    async fn list_playlists(&self) -> AppResult<GetPlaylistResponse> {
+        self.context.list_playlists(self.origin.clone()).await
+    }
+
+    async fn disconnect(&self) -> AppResult<StatusCode> {
+        self.context
+            .backend_client
+            .delete(&self.route("disconnect"))
+            .await
+    }
+
+    async fn status(&self) -> AppResult<PlatformStatus> {
+        self.context
+            .backend_client
+            .get_with_options(&self.route("status"), RequestOptions::quiet())
+            .await
+    }
+}
@@ -0,0 +1,249 @@
+use crate::backend::backend::BackendClient;
+use crate::backend::oauth::{OAuthStates, PlatformAuthFailure};
+use crate::backend::{AppleService, OAuthPlatformClient, SpotifyClient, YoutubeClient};
+use crate::error::{AppError, AppResult};
+use async_trait::async_trait;
+use futures::future::join_all;
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use swaptun_backend::{GetPlaylistResponse, GetPlaylistsParams, PlaylistOrigin};
+use tauri::http::StatusCode;
+use tauri::{AppHandle, Emitter, Url};
+use tauri_plugin_custom_tabs_manager::{CustomTabsManagerExt, OpenCustomTabSimpleRequest};
+
+/// Event carrying a platform's playlists once they were imported
+pub const PLATFORM_PLAYLISTS_EVENT: &str = "platform_playlists";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformPlaylists {
+    pub origin: PlaylistOrigin,
+    pub playlists: GetPlaylistResponse,
+}
+
+/// Authorization URL built by the backend, which alone knows the platform app secret
+#[derive(Debug, Deserialize)]
+pub struct AuthorizationUrlResponse {
+    pub url: String,
+}
+
+/// Event carrying a platform's connection after it was linked, unlinked or its token failed
+pub const PLATFORM_CONNECTION_CHANGED_EVENT: &str = "platform_connection_changed";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    Connected,
+    /// The platform token expired and could not be refreshed
+    Expired,
+    /// The user withdrew swaptun's access from the platform
+    Revoked,
+    Disconnected,
+    /// The backend could not tell, e.g. its status endpoint failed
+    Unknown,
+}
+
+/// How the user's account on a platform is linked, as the backend knows it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformStatus {
+    pub state: ConnectionState,
+    /// Account name on the platform
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub last_synced_at: Option<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+impl PlatformStatus {
+    fn with_state(state: ConnectionState) -> Self {
+        Self {
+            state,
+            display_name: None,
+            last_synced_at: None,
+            scopes: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformConnection {
+    pub origin: PlaylistOrigin,
+    #[serde(flatten)]
+    pub status: PlatformStatus,
+}
+
+/// A streaming service the user can link and import playlists from
+#[async_trait]
+pub trait MusicPlatform: Send + Sync {
+    fn origin(&self) -> PlaylistOrigin;
+
+    /// Start linking the user's account, usually by opening the platform consent screen
+    async fn connect(&self, request_id: Option<String>) -> AppResult<()>;
+
+    /// Finish an authorization from the redirect deep link
+    async fn handle_callback(&self, callback: &Url) -> Result<(), PlatformAuthFailure>;
+
+    /// Have the backend pull the user's playlists from the platform
+    async fn import_playlists(&self, request_id: Option<String>) -> AppResult<StatusCode>;
+
+    async fn list_playlists(&self) -> AppResult<GetPlaylistResponse>;
+
+    async fn disconnect(&self) -> AppResult<StatusCode>;
+
+    async fn status(&self) -> AppResult<PlatformStatus>;
+}
+
+/// Every supported platform, looked up by origin
+pub struct PlatformRegistry {
+    platforms: Vec<Box<dyn MusicPlatform>>,
+}
+
+impl PlatformRegistry {
+    pub fn new(context: PlatformContext) -> Self {
+        Self {
+            platforms: vec![
+                Box::new(SpotifyClient::new(context.clone())),
+                Box::new(YoutubeClient::new(context.clone())),
+                Box::new(AppleService::new(context.clone())),
+                Box::new(OAuthPlatformClient::new(
+                    context.clone(),
+                    PlaylistOrigin::Deezer,
+                    "deezer",
+                )),
+                Box::new(OAuthPlatformClient::new(
+                    context.clone(),
+                    PlaylistOrigin::Tidal,
+                    "tidal",
+                )),
+                Box::new(OAuthPlatformClient::new(
+                    context.clone(),
+                    PlaylistOrigin::SoundCloud,
+                    "soundcloud",
+                )),
+                Box::new(OAuthPlatformClient::new(
+                    context,
+                    PlaylistOrigin::AmazonMusic,
+                    "amazon-music",
+                )),
+            ],
+        }
+    }
+
+    pub fn get(&self, origin: &PlaylistOrigin) -> AppResult<&dyn MusicPlatform> {
+        self.platforms
+            .iter()
+            .find(|platform| platform.origin() == *origin)
+            .map(|platform| platform.as_ref())
+            .ok_or_else(|| AppError::Validation(format!("{:?} is not supported", origin).into()))
+    }
+
+    pub async fn connection(&self, origin: &PlaylistOrigin) -> AppResult<PlatformConnection> {
+        Self::connection_of(self.get(origin)?).await
+    }
+
+    /// Connection of every supported platform, in registry order, all probed at once. A
+    /// platform whose status fails is reported as unknown so the others still show up
+    pub async fn connections(&self) -> Vec<PlatformConnection> {
+        join_all(self.platforms.iter().map(|platform| async move {
+            match Self::connection_of(platform.as_ref()).await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    error!("Error getting {:?} connection: {}", platform.origin(), e);
+                    PlatformConnection {
+                        origin: platform.origin(),
+                        status: PlatformStatus::with_state(ConnectionState::Unknown),
+                    }
+                }
+            }
+        }))
+        .await
+    }
+
+    /// The backend has no status for platforms that were never linked
+    async fn connection_of(platform: &dyn MusicPlatform) -> AppResult<PlatformConnection> {
+        let status = match platform.status().await {
+            Ok(status) => status,
+            Err(AppError::NotFound(_)) => PlatformStatus::with_state(ConnectionState::Disconnected),
+            Err(e) => return Err(e),
+        };
+        Ok(PlatformConnection {
+            origin: platform.origin(),
+            status,
+        })
+    }
+}
+
+/// Services shared by the platform clients
+#[derive(Clone)]
+pub struct PlatformContext {
+    pub backend_client: Arc<BackendClient>,
+    pub app_handle: AppHandle,
+    pub oauth: Arc<OAuthStates>,
+}
+
+impl PlatformContext {
+    /// Bind a backend-issued authorization URL to this device and open it
+    pub fn open_authorization(&self, origin: PlaylistOrigin, auth_url: &str) -> AppResult<()> {
+        let url = self.oauth.begin(origin, auth_url)?;
+        self.app_handle
+            .custom_tabs_manager()
+            .open_custom_tab_simple(OpenCustomTabSimpleRequest {
+                url,
+                try_native_app: true,
+            })
+            .map_err(|e| AppError::Internal(format!("Failed to open custom tab: {}", e).into()))?;
+        Ok(())
+    }
+
+    /// Validate an OAuth callback and let the backend exchange its code at `token_endpoint`
+    pub async fn exchange_code(
+        &self,
+        origin: PlaylistOrigin,
+        callback: &Url,
+        token_endpoint: &str,
+    ) -> Result<(), PlatformAuthFailure> {
+        let request = self
+            .oauth
+            .complete(origin.clone(), callback)?
+            .into_token_request();
+        match self
+            .backend_client
+            .post_json(token_endpoint, &request)
+            .await
+        {
+            Ok(status) => {
+                info!("{:?} token set with status: {:?}", origin, status);
+                Ok(())
+            }
+            Err(e) => {
+                error!("Error setting {:?} token: {}", origin, e);
+                Err(PlatformAuthFailure::ExchangeFailed)
+            }
+        }
+    }
+
+    pub async fn list_playlists(&self, origin: PlaylistOrigin) -> AppResult<GetPlaylistResponse> {
+        let params = GetPlaylistsParams {
+            origin: Some(origin),
+            include_musics: true,
+        };
+        self.backend_client
+            .get_with_query("playlists", &params)
+            .await
+    }
+
+    /// Send the freshly imported playlists of a platform to the frontend
+    pub async fn emit_playlists(&self, origin: PlaylistOrigin) {
+        match self.list_playlists(origin.clone()).await {
+            Ok(playlists) => {
+                let event = PlatformPlaylists { origin, playlists };
+                if let Err(e) = self.app_handle.emit(PLATFORM_PLAYLISTS_EVENT, event) {
+                    error!("Failed to emit platform_playlists event: {}", e);
+                }
+            }
+            Err(e) => error!("Error getting {:?} playlists: {}", origin, e),
+        }
+    }
+}
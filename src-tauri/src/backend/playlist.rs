@@ -1,45 +1,85 @@
-use crate::backend::backend::BackendClient;
+use crate::backend::backend::{BackendClient, RequestOptions};
+use crate::backend::timeouts::EndpointClass;
 use crate::error::AppResult;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use swaptun_backend::{
-    GetPlaylistMusicsResponse, GetPlaylistResponse, GetPlaylistsParams, SendPlaylistRequest,
-    SendPlaylistResponse, SharedPlaylistsResponse,
+    GetPlaylistMusicsResponse, SendPlaylistRequest, SendPlaylistResponse, SharedPlaylistsResponse,
 };
 use swaptun_backend::{GetSharedPlaylistsParams, SharePlaylistRequest};
-use tauri::{http::StatusCode, AppHandle};
+use tauri::http::StatusCode;
+
+/// Public prefix of playlist share links, routed back into the app as deep links
+pub const SHARE_LINK_BASE_URL: &str = "https://swaptun.com/p";
+
+/// Who can find a playlist through its share link
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareLinkVisibility {
+    /// Listed on the owner's public profile
+    Public,
+    /// Only reachable by people who received the link
+    #[default]
+    Unlisted,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateShareLinkRequest {
+    pub visibility: ShareLinkVisibility,
+    /// Link lifetime, never expires when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_in_hours: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShareLink {
+    pub token: String,
+    pub playlist_id: i32,
+    pub visibility: ShareLinkVisibility,
+    pub expires_at: Option<String>,
+    /// Full link to send, filled in by the app
+    #[serde(default)]
+    pub url: String,
+}
+
+/// Playlist a share link points to, now visible in the user's shared playlists
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedShareLink {
+    pub playlist_id: i32,
+    pub playlist_name: String,
+    pub owner_username: String,
+}
+
 pub struct PlaylistService {
-    backend_client: BackendClient,
+    backend_client: Arc<BackendClient>,
     base_url: String,
 }
 
 impl PlaylistService {
-    pub fn new(app_handle: AppHandle) -> Self {
+    pub fn new(backend_client: Arc<BackendClient>) -> Self {
         Self {
-            backend_client: BackendClient::new(app_handle),
+            backend_client,
             base_url: "playlists".into(),
         }
     }
 
-    pub async fn get_playlists(
-        &self,
-        params: GetPlaylistsParams,
-    ) -> AppResult<GetPlaylistResponse> {
-        self.backend_client
-            .get_with_body::<GetPlaylistResponse, _>(
-                &self.base_url,
-                serde_json::to_string(&params).unwrap(),
-            )
-            .await
-    }
-
     pub async fn send_playlist(
         &self,
         playlist_id: i32,
         req: SendPlaylistRequest,
+        request_id: Option<String>,
     ) -> AppResult<SendPlaylistResponse> {
         let url = format!("{}/{}/send", self.base_url, playlist_id);
         self.backend_client
-            .post_with_return(&url, serde_json::to_string(&req).unwrap())
+            .post_json_with_return_options(
+                &url,
+                &req,
+                RequestOptions {
+                    class: EndpointClass::LongRunning,
+                    request_id,
+                    ..RequestOptions::idempotent()
+                },
+            )
             .await
     }
 
@@ -49,9 +89,7 @@ impl PlaylistService {
         req: SharePlaylistRequest,
     ) -> AppResult<StatusCode> {
         let url = format!("{}/{}/share", self.base_url, playlist_id);
-        self.backend_client
-            .post(&url, serde_json::to_string(&req).unwrap())
-            .await
+        self.backend_client.post_json(&url, &req).await
     }
 
     pub async fn get_shared_playlists(&self) -> AppResult<SharedPlaylistsResponse> {
@@ -60,7 +98,7 @@ impl PlaylistService {
         };
         let url = format!("{}/shared", self.base_url);
         self.backend_client
-            .get_with_body(&url, serde_json::to_string(&get_shared_playlist).unwrap())
+            .get_with_query(&url, &get_shared_playlist)
             .await
     }
 
@@ -79,4 +117,29 @@ impl PlaylistService {
         let url = format!("{}/{}/musics", self.base_url, playlist_id);
         self.backend_client.get(&url).await
     }
+
+    pub async fn create_share_link(
+        &self,
+        playlist_id: i32,
+        req: CreateShareLinkRequest,
+    ) -> AppResult<ShareLink> {
+        let url = format!("{}/{}/links", self.base_url, playlist_id);
+        let mut link: ShareLink = self
+            .backend_client
+            .post_json_with_return_options(&url, &req, RequestOptions::idempotent())
+            .await?;
+        link.url = format!("{}/{}", SHARE_LINK_BASE_URL, link.token);
+        Ok(link)
+    }
+
+    /// Look up the playlist behind a share link and add it to the user's shared playlists
+    pub async fn resolve_share_link(&self, token: &str) -> AppResult<ResolvedShareLink> {
+        let url = format!("{}/links/{}", self.base_url, token);
+        self.backend_client.get(&url).await
+    }
+
+    pub async fn revoke_share_link(&self, token: &str) -> AppResult<StatusCode> {
+        let url = format!("{}/links/{}", self.base_url, token);
+        self.backend_client.delete(&url).await
+    }
 }
@@ -0,0 +1,98 @@
+use crate::models::ErrorNotification;
+use log::{info, warn};
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tauri::{async_runtime::spawn, AppHandle, Emitter};
+use tauri_plugin_http::reqwest::Client;
+use tokio::sync::watch;
+use tokio::time::{sleep, timeout};
+
+/// Event emitted whenever the backend goes online or offline
+pub const CONNECTIVITY_CHANGED_EVENT: &str = "connectivity_changed";
+
+const HEALTH_ENDPOINT: &str = "health";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+const ONLINE_PROBE_INTERVAL: Duration = Duration::from_secs(30);
+const OFFLINE_PROBE_INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ConnectivityStatus {
+    pub online: bool,
+}
+
+/// Background monitor caching whether the backend health endpoint answers
+pub struct ReachabilityMonitor {
+    app_handle: AppHandle,
+    state: watch::Sender<bool>,
+}
+
+impl ReachabilityMonitor {
+    pub fn new(app_handle: AppHandle) -> Arc<Self> {
+        // Assume online until the first probe says otherwise
+        let (state, _) = watch::channel(true);
+        Arc::new(Self { app_handle, state })
+    }
+
+    /// Start probing the backend health endpoint in the background
+    pub fn start(self: &Arc<Self>, client: Arc<RwLock<Client>>, base_url: Arc<RwLock<String>>) {
+        let monitor = self.clone();
+        spawn(async move {
+            loop {
+                let client = client.read().unwrap().clone();
+                let url = format!("{}/{}", base_url.read().unwrap(), HEALTH_ENDPOINT);
+
+                // Any HTTP answer proves the backend is reachable, even one without a
+                // health route, only transport failures mean offline
+                let online = client.get(&url).timeout(PROBE_TIMEOUT).send().await.is_ok();
+                monitor.report(online);
+
+                let interval = if online {
+                    ONLINE_PROBE_INTERVAL
+                } else {
+                    OFFLINE_PROBE_INTERVAL
+                };
+                sleep(interval).await;
+            }
+        });
+    }
+
+    pub fn is_online(&self) -> bool {
+        *self.state.borrow()
+    }
+
+    /// Record the latest known state, emitting an event when it changes
+    pub fn report(&self, online: bool) {
+        let changed = self.state.send_if_modified(|current| {
+            if *current == online {
+                false
+            } else {
+                *current = online;
+                true
+            }
+        });
+        if !changed {
+            return;
+        }
+
+        if online {
+            info!("Backend reachable again");
+        } else {
+            warn!("Backend unreachable");
+            let error = ErrorNotification::network_error("No internet connection");
+            let _ = self.app_handle.emit("error_notification", error);
+        }
+        let _ = self
+            .app_handle
+            .emit(CONNECTIVITY_CHANGED_EVENT, ConnectivityStatus { online });
+    }
+
+    /// Wait until the backend is reachable, returning false on timeout
+    pub async fn wait_until_online(&self, max_wait: Duration) -> bool {
+        let mut receiver = self.state.subscribe();
+        matches!(
+            timeout(max_wait, receiver.wait_for(|online| *online)).await,
+            Ok(Ok(_))
+        )
+    }
+}
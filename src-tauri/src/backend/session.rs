@@ -0,0 +1,185 @@
+use crate::backend::vault::{CredentialKey, CredentialVault};
+use crate::error::{AppError, AppResult, ErrorCode};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_http::reqwest::Client;
+use tauri_plugin_pinia::ManagerExt;
+use tokio::sync::Mutex;
+
+/// Event emitted when the session could not be refreshed and the user must log in again
+pub const SESSION_EXPIRED_EVENT: &str = "session_expired";
+
+/// Refresh the token when it expires within this margin
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+const REFRESH_TIMEOUT: Duration = Duration::from_secs(10);
+const REFRESH_ENDPOINT: &str = "auth/refresh";
+
+#[derive(Debug, Deserialize)]
+struct Claims {
+    exp: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RefreshTokenResponse {
+    token: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionExpired {
+    pub reason: String,
+}
+
+/// Expiry of a JWT, read from its `exp` claim without verifying the signature
+pub fn token_expiry(token: &str) -> Option<SystemTime> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    let claims: Claims = serde_json::from_slice(&bytes).ok()?;
+    claims.exp.map(|exp| UNIX_EPOCH + Duration::from_secs(exp))
+}
+
+/// Keeps the user's session token fresh
+pub struct SessionManager {
+    app_handle: AppHandle,
+    vault: Arc<CredentialVault>,
+    /// Serializes refreshes so concurrent requests share a single refresh call
+    refresh_lock: Mutex<()>,
+}
+
+impl SessionManager {
+    pub fn new(app_handle: AppHandle, vault: Arc<CredentialVault>) -> Self {
+        let manager = Self {
+            app_handle,
+            vault,
+            refresh_lock: Mutex::new(()),
+        };
+        manager.migrate_legacy_token();
+        manager
+    }
+
+    /// Move a token left in the pinia store by older versions into the vault
+    fn migrate_legacy_token(&self) {
+        let pinia = self.app_handle.pinia();
+        let Some(token) = pinia
+            .get("user", "token")
+            .and_then(|token| token.as_str().map(str::to_string))
+        else {
+            return;
+        };
+
+        if let Err(e) = self.start(&token) {
+            error!("Failed to migrate session token to the vault: {}", e);
+            return;
+        }
+        let _ = pinia.set("user", "token", Value::Null);
+        let _ = pinia.set("user", "authenticated", true.into());
+        info!("Session token migrated to the credential vault");
+    }
+
+    fn stored_token(&self) -> Option<String> {
+        self.vault
+            .load(CredentialKey::SessionToken)
+            .filter(|token| !token.is_empty())
+    }
+
+    pub fn has_session(&self) -> bool {
+        self.stored_token().is_some()
+    }
+
+    /// Keep the token of a freshly opened session
+    pub fn start(&self, token: &str) -> AppResult<()> {
+        self.vault.store(CredentialKey::SessionToken, token)
+    }
+
+    /// Forget the session token and tell the frontend the user must log in again
+    pub fn expire(&self, reason: String) {
+        if let Err(e) = self.vault.clear(CredentialKey::SessionToken) {
+            error!("Failed to clear the expired session token: {}", e);
+        }
+        let _ = self
+            .app_handle
+            .emit(SESSION_EXPIRED_EVENT, SessionExpired { reason });
+    }
+
+    fn expires_soon(token: &str) -> bool {
+        match token_expiry(token) {
+            Some(expiry) => expiry <= SystemTime::now() + REFRESH_MARGIN,
+            // Tokens without a readable expiry are left to the backend to judge
+            None => false,
+        }
+    }
+
+    /// Current session token, refreshed first if it is about to expire
+    pub async fn access_token(&self, client: &Client, base_url: &str) -> Option<String> {
+        let token = self.stored_token()?;
+        if !Self::expires_soon(&token) {
+            return Some(token);
+        }
+
+        info!("Session token expires soon, refreshing");
+        // On failure let the request go out, the 401 path reports the expired session
+        Some(
+            self.refresh(client, base_url, &token)
+                .await
+                .unwrap_or(token),
+        )
+    }
+
+    /// Exchange `stale_token` for a new one, emitting `session_expired` on failure
+    pub async fn refresh(
+        &self,
+        client: &Client,
+        base_url: &str,
+        stale_token: &str,
+    ) -> AppResult<String> {
+        let _guard = self.refresh_lock.lock().await;
+
+        // Another request refreshed the session while we were waiting
+        if let Some(token) = self.stored_token() {
+            if token != stale_token && !Self::expires_soon(&token) {
+                return Ok(token);
+            }
+        }
+
+        match self.request_refresh(client, base_url, stale_token).await {
+            Ok(token) => {
+                self.start(&token)?;
+                info!("Session token refreshed");
+                Ok(token)
+            }
+            Err(e) => {
+                warn!("Session refresh failed: {}", e);
+                self.expire(e.to_string());
+                Err(AppError::Auth("Session expired".into()).with_code(ErrorCode::TokenExpired))
+            }
+        }
+    }
+
+    async fn request_refresh(
+        &self,
+        client: &Client,
+        base_url: &str,
+        stale_token: &str,
+    ) -> AppResult<String> {
+        let response = client
+            .post(format!("{}/{}", base_url, REFRESH_ENDPOINT))
+            .bearer_auth(stale_token)
+            .timeout(REFRESH_TIMEOUT)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(AppError::from_response(status, text));
+        }
+
+        let body = response.json::<RefreshTokenResponse>().await?;
+        Ok(body.token)
+    }
+}
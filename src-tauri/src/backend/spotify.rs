@@ -1,11 +1,18 @@
-use crate::backend::backend::BackendClient;
+use crate::backend::backend::RequestOptions;
+use crate::backend::oauth::PlatformAuthFailure;
+use crate::backend::platform::{MusicPlatform, PlatformContext, PlatformStatus};
 use crate::error::AppResult;
+use async_trait::async_trait;
+use log::{error, info};
 use serde::Deserialize;
-use tauri::{http::StatusCode, AppHandle};
-
-use swaptun_backend::{AddTokenRequest, SpotifyUrlResponse};
+use swaptun_backend::{GetPlaylistResponse, PlaylistOrigin, SpotifyUrlResponse};
+use tauri::http::StatusCode;
+use tauri::Url;
 use tauri_plugin_http::reqwest::Body;
 
+/// Request id of the playlist import triggered by the Spotify callback, cancellable from the UI
+pub const SPOTIFY_IMPORT_REQUEST_ID: &str = "spotify_import";
+
 #[derive(Debug, Deserialize)]
 pub struct SpotifyAuthResponse {
     pub _access_token: String,
@@ -14,37 +21,76 @@ pub struct SpotifyAuthResponse {
 }
 
 pub struct SpotifyClient {
-    backend_client: BackendClient,
+    context: PlatformContext,
 }
 
 impl SpotifyClient {
-    pub fn new(app_handle: AppHandle) -> Self {
-        Self {
-            backend_client: BackendClient::new(app_handle),
-        }
+    pub fn new(context: PlatformContext) -> Self {
+        Self { context }
     }
 
     pub async fn get_auth_url(&self) -> AppResult<SpotifyUrlResponse> {
-        self.backend_client
+        self.context
+            .backend_client
             .get::<SpotifyUrlResponse>("spotify/authorization-url")
             .await
     }
+}
+
+#[async_trait]
+impl MusicPlatform for SpotifyClient {
+    fn origin(&self) -> PlaylistOrigin {
+        PlaylistOrigin::Spotify
+    }
+
+    async fn connect(&self, _request_id: Option<String>) -> AppResult<()> {
+        let response = self.get_auth_url().await?;
+        self.context
+            .open_authorization(PlaylistOrigin::Spotify, &response.url)
+    }
 
-    pub async fn add_token(&self, req: AddTokenRequest) -> AppResult<StatusCode> {
-        self.backend_client
-            .post("spotify/token", serde_json::to_string(&req).unwrap())
+    /// Exchange the code, then import the user's playlists right away
+    async fn handle_callback(&self, callback: &Url) -> Result<(), PlatformAuthFailure> {
+        self.context
+            .exchange_code(PlaylistOrigin::Spotify, callback, "spotify/token")
+            .await?;
+        match self
+            .import_playlists(Some(SPOTIFY_IMPORT_REQUEST_ID.to_string()))
             .await
+        {
+            Ok(status) => info!("Spotify playlists imported with status: {:?}", status),
+            Err(e) => error!("Error importing Spotify playlists: {}", e),
+        }
+        self.context.emit_playlists(PlaylistOrigin::Spotify).await;
+        Ok(())
     }
 
-    pub async fn import_playlist_backend_request(&self) -> AppResult<StatusCode> {
-        self.backend_client
-            .post("spotify/playlist", Body::from(""))
+    async fn import_playlists(&self, request_id: Option<String>) -> AppResult<StatusCode> {
+        self.context
+            .backend_client
+            .post_with_options(
+                "spotify/playlist",
+                Body::from(""),
+                RequestOptions::long_running(request_id),
+            )
             .await
     }
 
-    pub async fn disconnect(&self) -> AppResult<StatusCode> {
-        self.backend_client
+    async fn list_playlists(&self) -> AppResult<GetPlaylistResponse> {
+        self.context.list_playlists(PlaylistOrigin::Spotify).await
+    }
+
+    async fn disconnect(&self) -> AppResult<StatusCode> {
+        self.context
+            .backend_client
             .delete("spotify/disconnect")
             .await
     }
+
+    async fn status(&self) -> AppResult<PlatformStatus> {
+        self.context
+            .backend_client
+            .get_with_options("spotify/status", RequestOptions::quiet())
+            .await
+    }
 }
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+/// Kind of endpoint, deciding how long a request may take
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EndpointClass {
+    /// Regular API calls answering within a few seconds
+    #[default]
+    Standard,
+    /// Playlist imports, synchronizations and sends
+    LongRunning,
+}
+
+/// Timeouts of the pooled client and of each class of endpoints
+#[derive(Debug, Clone)]
+pub struct TimeoutConfig {
+    /// Time allowed to establish a connection, for every request of the shared client
+    pub connect: Duration,
+    /// Time allowed between two reads of a response, for every request of the shared client,
+    /// so it must leave long-running calls room to work before answering
+    pub read: Duration,
+    /// Time allowed for a whole standard request, body included
+    pub standard: Duration,
+    /// Time allowed for a whole long-running request, body included
+    pub long_running: Duration,
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        Self {
+            connect: Duration::from_secs(10),
+            read: Duration::from_secs(300),
+            standard: Duration::from_secs(30),
+            long_running: Duration::from_secs(600),
+        }
+    }
+}
+
+impl TimeoutConfig {
+    /// Time allowed for a whole request of the given class
+    pub fn total(&self, class: EndpointClass) -> Duration {
+        match class {
+            EndpointClass::Standard => self.standard,
+            EndpointClass::LongRunning => self.long_running,
+        }
+    }
+}
@@ -1,55 +1,224 @@
-use crate::backend::backend::BackendClient;
-use crate::error::AppResult;
-use log::info;
+use crate::backend::backend::{AuthContext, BackendClient, RequestOptions};
+use crate::error::{AppError, AppResult, ErrorCode};
+use crate::utils::{qr_code_images, QrCodeImages};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use swaptun_backend::{
-    AddFriendRequest, CreateUserRequest, ForgotPasswordRequest, GetUsersRequest, LoginEmailRequest,
-    LoginRequest, LoginResponse, RemoveFriendRequest, ResetPasswordRequest, UserBean,
-    VerifyTokenRequest, VerifyTokenResponse,
+    CreateUserRequest, ForgotPasswordRequest, GetUsersRequest, LoginEmailRequest, LoginRequest,
+    LoginResponse, RemoveFriendRequest, ResetPasswordRequest, UserBean, VerifyTokenRequest,
+    VerifyTokenResponse,
 };
-use tauri::AppHandle;
 use tauri_plugin_http::reqwest::StatusCode;
 
+/// Result of a successful login, the session token itself stays in the credential vault
+#[derive(Debug, Serialize)]
+pub struct AuthenticatedUser {
+    pub user: UserBean,
+}
+
+/// Users returned per page of a search
+pub const USER_SEARCH_PAGE_SIZE: u64 = 20;
+/// Quiet period before a query is sent, so fast typing only reaches the backend once
+const USER_SEARCH_DEBOUNCE: Duration = Duration::from_millis(300);
+/// Request id of the search in flight, cancelled as soon as a newer query comes in
+const USER_SEARCH_REQUEST_ID: &str = "user_search";
+
+#[derive(Debug, Deserialize)]
+struct UsersPageResponse {
+    users: Vec<UserBean>,
+    total: u64,
+}
+
+/// One page of user search results
+#[derive(Debug, Serialize)]
+pub struct UserPage {
+    pub users: Vec<UserBean>,
+    /// Pass back to get the next page, `None` on the last one
+    pub next_cursor: Option<String>,
+    /// Matches across all pages, less the blocked users hidden from this one
+    pub total: u64,
+}
+
+/// Side of a friend request, seen from the current user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FriendRequestDirection {
+    /// Sent to the user, waiting for their answer
+    Incoming,
+    /// Sent by the user, waiting for the other side
+    Outgoing,
+}
+
+/// Pending friend request, `user` is the other party
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FriendRequest {
+    pub id: i32,
+    pub direction: FriendRequestDirection,
+    pub user: UserBean,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SendFriendRequest {
+    user_id: i32,
+}
+
+#[derive(Debug, Serialize)]
+struct FriendRequestsQuery {
+    direction: FriendRequestDirection,
+}
+
+#[derive(Debug, Serialize)]
+struct BlockUserRequest {
+    user_id: i32,
+}
+
+/// Why a user is reported to moderation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    InappropriateContent,
+    Impersonation,
+    Other,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportUserRequest {
+    pub reason: ReportReason,
+    /// Free text from the user, required by moderation when the reason is `Other`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+/// Public prefix of friend invite links, routed back into the app as deep links
+pub const INVITE_LINK_BASE_URL: &str = "https://swaptun.com/invite";
+
+/// Personal invite token as issued by the backend
+#[derive(Debug, Deserialize)]
+struct InviteToken {
+    token: String,
+    expires_at: Option<String>,
+}
+
+/// Invite link of the current user, with a QR code to show to people nearby
+#[derive(Debug, Serialize)]
+pub struct FriendInvite {
+    pub token: String,
+    pub url: String,
+    pub expires_at: Option<String>,
+    pub qr_code: QrCodeImages,
+}
+
+/// Owner of an accepted invite, who now has a pending friend request from the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InviteSender {
+    pub user_id: i32,
+    pub username: String,
+}
+
 pub struct UserService {
-    backend_client: BackendClient,
+    backend_client: Arc<BackendClient>,
+    /// Ids of blocked users, fetched on first use and kept in sync by block/unblock
+    blocked_ids: Mutex<Option<HashSet<i32>>>,
+    /// Bumped by every search, a query only goes out if it is still the latest
+    search_generation: AtomicU64,
 }
 
 impl UserService {
-    pub fn new(app_handle: AppHandle) -> Self {
+    pub fn new(backend_client: Arc<BackendClient>) -> Self {
         Self {
-            backend_client: BackendClient::new(app_handle),
+            backend_client,
+            blocked_ids: Mutex::new(None),
+            search_generation: AtomicU64::new(0),
         }
     }
 
     pub async fn register(&self, request: CreateUserRequest) -> AppResult<StatusCode> {
         self.backend_client
-            .post("register", serde_json::to_string(&request).unwrap())
+            .post_json_with_options("register", &request, Self::anonymous())
             .await
     }
 
-    pub async fn login(&self, login_request: LoginRequest) -> AppResult<LoginResponse> {
-        self.backend_client
-            .post_with_return::<LoginResponse, _>(
+    pub async fn login(&self, login_request: LoginRequest) -> AppResult<AuthenticatedUser> {
+        let response = self
+            .backend_client
+            .post_json_with_return_options::<LoginResponse, _>(
                 "auth/login",
-                serde_json::to_string(&login_request).unwrap(),
+                &login_request,
+                Self::anonymous(),
             )
             .await
+            .map_err(Self::map_login_error)?;
+        self.start_session(response)
     }
 
-    pub async fn login_email(&self, login_request: LoginEmailRequest) -> AppResult<LoginResponse> {
-        self.backend_client
-            .post_with_return::<LoginResponse, _>(
+    pub async fn login_email(
+        &self,
+        login_request: LoginEmailRequest,
+    ) -> AppResult<AuthenticatedUser> {
+        let response = self
+            .backend_client
+            .post_json_with_return_options::<LoginResponse, _>(
                 "auth/login_email",
-                serde_json::to_string(&login_request).unwrap(),
+                &login_request,
+                Self::anonymous(),
             )
             .await
+            .map_err(Self::map_login_error)?;
+        self.start_session(response)
+    }
+
+    fn start_session(&self, response: LoginResponse) -> AppResult<AuthenticatedUser> {
+        self.backend_client.start_session(&response.token)?;
+        info!("Session started for user {}", response.user.id);
+        Ok(AuthenticatedUser {
+            user: response.user,
+        })
+    }
+
+    /// Revoke the session on the backend, then forget it locally even if that failed
+    pub async fn logout(&self) -> AppResult<()> {
+        if let Err(e) = self.backend_client.post_json("auth/logout", &()).await {
+            warn!("Failed to revoke session on the backend: {}", e);
+        }
+        self.blocked_ids.lock().unwrap().take();
+        self.backend_client.clear_credentials()
     }
 
-    pub async fn verify_token(&self, request: VerifyTokenRequest) -> AppResult<VerifyTokenResponse> {
+    /// Options for endpoints called before the user has a session
+    fn anonymous() -> RequestOptions {
+        RequestOptions {
+            auth: AuthContext::Anonymous,
+            ..Default::default()
+        }
+    }
+
+    /// A 401 on login means wrong credentials, not an expired session
+    fn map_login_error(error: AppError) -> AppError {
+        match error.status() {
+            Some(401) => error.with_code(ErrorCode::InvalidCredentials),
+            _ => error,
+        }
+    }
+
+    pub async fn verify_token(
+        &self,
+        request: VerifyTokenRequest,
+    ) -> AppResult<VerifyTokenResponse> {
         let response = self
             .backend_client
-            .post_with_return::<VerifyTokenResponse, _>(
+            .post_json_with_return_options::<VerifyTokenResponse, _>(
                 "auth/verify_token",
-                serde_json::to_string(&request).unwrap(),
+                &request,
+                Self::anonymous(),
             )
             .await?;
         Ok(response)
@@ -57,48 +226,245 @@ impl UserService {
 
     pub async fn forgot_password(&self, req: ForgotPasswordRequest) -> AppResult<StatusCode> {
         self.backend_client
-            .post("auth/forgot-password", serde_json::to_string(&req).unwrap())
+            .post_json_with_options("auth/forgot-password", &req, Self::anonymous())
             .await
     }
 
-    pub async fn reset_password(&self, token: String, request: ResetPasswordRequest) -> AppResult<StatusCode> {
-        self.backend_client.set_temporary_token(token).await;
+    pub async fn reset_password(
+        &self,
+        token: String,
+        request: ResetPasswordRequest,
+    ) -> AppResult<StatusCode> {
         self.backend_client
-            .post(
+            .post_json_with_options(
                 "users/reset-password",
-                serde_json::to_string(&request).unwrap(),
+                &request,
+                RequestOptions {
+                    auth: AuthContext::Bearer(token),
+                    ..Default::default()
+                },
             )
             .await
     }
 
-    pub async fn get_users(&self, request: GetUsersRequest) -> AppResult<Vec<UserBean>> {
-        info!("Fetching users with request: {:?}", request);
-        self.backend_client
-            .get_with_body("users", serde_json::to_string(&request).unwrap())
-            .await
+    /// Search users one page at a time
+    ///
+    /// A first page waits for typing to settle and is dropped if a newer search
+    /// started meanwhile, superseded searches fail with `ErrorCode::Cancelled`.
+    pub async fn search_users(
+        &self,
+        mut request: GetUsersRequest,
+        cursor: Option<String>,
+    ) -> AppResult<UserPage> {
+        info!("Searching users with request: {:?}", request);
+        // Only a new query supersedes the previous one, loading more pages of it does not
+        let offset = match cursor {
+            Some(cursor) => Self::decode_cursor(&cursor)?,
+            None => {
+                self.debounce_search().await?;
+                0
+            }
+        };
+        request.limit = Some(USER_SEARCH_PAGE_SIZE as _);
+        request.offset = Some(offset as _);
+
+        let page: UsersPageResponse = self
+            .backend_client
+            .get_with_query_options(
+                "users/search",
+                &request,
+                RequestOptions {
+                    request_id: Some(USER_SEARCH_REQUEST_ID.to_string()),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let fetched = page.users.len();
+        let next_offset = offset + fetched as u64;
+        let next_cursor = (fetched > 0 && next_offset < page.total)
+            .then(|| URL_SAFE_NO_PAD.encode(next_offset.to_string()));
+        let users = self.without_blocked(page.users).await;
+        let hidden = (fetched - users.len()) as u64;
+        Ok(UserPage {
+            users,
+            next_cursor,
+            total: page.total.saturating_sub(hidden),
+        })
+    }
+
+    /// Wait out the debounce delay, failing if a newer query started meanwhile
+    async fn debounce_search(&self) -> AppResult<()> {
+        let generation = self.search_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.backend_client.cancel(USER_SEARCH_REQUEST_ID);
+
+        tokio::time::sleep(USER_SEARCH_DEBOUNCE).await;
+        if self.search_generation.load(Ordering::SeqCst) != generation {
+            return Err(
+                AppError::Network("Search superseded by a newer query".into())
+                    .with_code(ErrorCode::Cancelled),
+            );
+        }
+        Ok(())
+    }
+
+    fn decode_cursor(cursor: &str) -> AppResult<u64> {
+        URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .and_then(|offset| offset.parse().ok())
+            .ok_or_else(|| AppError::Validation("Invalid search cursor".into()))
+    }
+
+    /// Drop blocked users, or keep everyone when the blocked list can't be fetched
+    async fn without_blocked(&self, users: Vec<UserBean>) -> Vec<UserBean> {
+        match self.blocked_user_ids().await {
+            Ok(blocked) => users
+                .into_iter()
+                .filter(|user| !blocked.contains(&user.id))
+                .collect(),
+            Err(e) => {
+                warn!("Showing users unfiltered, blocked users unavailable: {}", e);
+                users
+            }
+        }
     }
 
     pub async fn get_friends(&self) -> AppResult<Vec<UserBean>> {
         self.backend_client.get("users/friends").await
     }
 
-    pub async fn add_friend(&self, request: AddFriendRequest) -> AppResult<()> {
+    /// Ask another user to become friends, the friendship exists once they accept
+    pub async fn send_friend_request(&self, user_id: i32) -> AppResult<FriendRequest> {
         self.backend_client
-            .post(
-                "users/friends/add",
-                serde_json::to_string(&request).unwrap(),
+            .post_json_with_return_options(
+                "users/friends/requests",
+                &SendFriendRequest { user_id },
+                RequestOptions::idempotent(),
             )
-            .await?;
-        Ok(())
+            .await
+    }
+
+    pub async fn get_friend_requests(
+        &self,
+        direction: FriendRequestDirection,
+    ) -> AppResult<Vec<FriendRequest>> {
+        self.backend_client
+            .get_with_query("users/friends/requests", &FriendRequestsQuery { direction })
+            .await
+    }
+
+    /// Accept an incoming request, returning the new friend
+    pub async fn accept_friend_request(&self, request_id: i32) -> AppResult<UserBean> {
+        let url = format!("users/friends/requests/{}/accept", request_id);
+        self.backend_client.post_json_with_return(&url, &()).await
+    }
+
+    pub async fn decline_friend_request(&self, request_id: i32) -> AppResult<StatusCode> {
+        let url = format!("users/friends/requests/{}/decline", request_id);
+        self.backend_client.post_json(&url, &()).await
+    }
+
+    /// Withdraw an outgoing request that was not answered yet
+    pub async fn cancel_friend_request(&self, request_id: i32) -> AppResult<StatusCode> {
+        let url = format!("users/friends/requests/{}", request_id);
+        self.backend_client.delete(&url).await
     }
 
     pub async fn remove_friend(&self, request: RemoveFriendRequest) -> AppResult<()> {
         self.backend_client
-            .post(
-                "users/friends/remove",
-                serde_json::to_string(&request).unwrap(),
+            .post_json("users/friends/remove", &request)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn create_invite(&self) -> AppResult<FriendInvite> {
+        let invite: InviteToken = self
+            .backend_client
+            .post_json_with_return_options("users/invites", &(), RequestOptions::idempotent())
+            .await?;
+        let url = format!("{}/{}", INVITE_LINK_BASE_URL, invite.token);
+        Ok(FriendInvite {
+            qr_code: qr_code_images(&url)?,
+            token: invite.token,
+            url,
+            expires_at: invite.expires_at,
+        })
+    }
+
+    /// Send a friend request to the owner of an invite token
+    pub async fn accept_invite(&self, token: &str) -> AppResult<InviteSender> {
+        let url = format!("users/invites/{}/accept", token);
+        self.backend_client.post_json_with_return(&url, &()).await
+    }
+
+    /// Block a user, ending any friendship with them
+    pub async fn block_user(&self, user_id: i32) -> AppResult<StatusCode> {
+        let status = self
+            .backend_client
+            .post_json_with_options(
+                "users/blocks",
+                &BlockUserRequest { user_id },
+                RequestOptions::idempotent(),
             )
             .await?;
+        if let Some(blocked) = self.blocked_ids.lock().unwrap().as_mut() {
+            blocked.insert(user_id);
+        }
+
+        // The user is blocked either way, a failure here must not hide it from the caller
+        if let Err(e) = self.end_friendship(user_id).await {
+            warn!(
+                "Failed to end friendship with blocked user {}: {}",
+                user_id, e
+            );
+        }
+        Ok(status)
+    }
+
+    async fn end_friendship(&self, user_id: i32) -> AppResult<()> {
+        let friends = self.get_friends().await?;
+        if friends.iter().any(|friend| friend.id == user_id) {
+            self.remove_friend(RemoveFriendRequest { friend_id: user_id })
+                .await?;
+            info!("Friendship with blocked user {} removed", user_id);
+        }
         Ok(())
     }
+
+    pub async fn unblock_user(&self, user_id: i32) -> AppResult<StatusCode> {
+        let url = format!("users/blocks/{}", user_id);
+        let status = self.backend_client.delete(&url).await?;
+        if let Some(blocked) = self.blocked_ids.lock().unwrap().as_mut() {
+            blocked.remove(&user_id);
+        }
+        Ok(status)
+    }
+
+    pub async fn get_blocked_users(&self) -> AppResult<Vec<UserBean>> {
+        let users: Vec<UserBean> = self.backend_client.get("users/blocks").await?;
+        *self.blocked_ids.lock().unwrap() = Some(users.iter().map(|user| user.id).collect());
+        Ok(users)
+    }
+
+    /// Ids of the users blocked by the current user, hidden from searches and shares
+    pub async fn blocked_user_ids(&self) -> AppResult<HashSet<i32>> {
+        if let Some(blocked) = self.blocked_ids.lock().unwrap().as_ref() {
+            return Ok(blocked.clone());
+        }
+        let users = self.get_blocked_users().await?;
+        Ok(users.iter().map(|user| user.id).collect())
+    }
+
+    pub async fn report_user(
+        &self,
+        user_id: i32,
+        request: ReportUserRequest,
+    ) -> AppResult<StatusCode> {
+        let url = format!("users/{}/report", user_id);
+        self.backend_client
+            .post_json_with_options(&url, &request, RequestOptions::idempotent())
+            .await
+    }
 }
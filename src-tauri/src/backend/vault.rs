@@ -0,0 +1,457 @@
+use crate::error::{AppError, AppResult};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use swaptun_backend::PlaylistOrigin;
+use tauri::{AppHandle, Manager};
+
+const VAULT_FILE: &str = "credentials.vault";
+const NONCE_LEN: usize = 12;
+
+/// Secret stored in the vault
+pub enum CredentialKey {
+    /// JWT of the logged-in user
+    SessionToken,
+    /// Token granted by a streaming platform
+    PlatformToken(PlaylistOrigin),
+    /// OAuth state and PKCE verifier of an authorization in progress
+    OAuthState(PlaylistOrigin),
+}
+
+impl CredentialKey {
+    fn name(&self) -> &'static str {
+        match self {
+            CredentialKey::SessionToken => "session_token",
+            CredentialKey::PlatformToken(PlaylistOrigin::Spotify) => "spotify_token",
+            CredentialKey::PlatformToken(PlaylistOrigin::Deezer) => "deezer_token",
+            CredentialKey::PlatformToken(PlaylistOrigin::YoutubeMusic) => "youtube_music_token",
+            CredentialKey::PlatformToken(PlaylistOrigin::AppleMusic) => "apple_music_token",
+            CredentialKey::PlatformToken(PlaylistOrigin::Tidal) => "tidal_token",
+            CredentialKey::PlatformToken(PlaylistOrigin::SoundCloud) => "soundcloud_token",
+            CredentialKey::PlatformToken(PlaylistOrigin::AmazonMusic) => "amazon_music_token",
+            CredentialKey::OAuthState(PlaylistOrigin::Spotify) => "spotify_oauth_state",
+            CredentialKey::OAuthState(PlaylistOrigin::Deezer) => "deezer_oauth_state",
+            CredentialKey::OAuthState(PlaylistOrigin::YoutubeMusic) => "youtube_music_oauth_state",
+            CredentialKey::OAuthState(PlaylistOrigin::AppleMusic) => "apple_music_oauth_state",
+            CredentialKey::OAuthState(PlaylistOrigin::Tidal) => "tidal_oauth_state",
+            CredentialKey::OAuthState(PlaylistOrigin::SoundCloud) => "soundcloud_oauth_state",
+            CredentialKey::OAuthState(PlaylistOrigin::AmazonMusic) => "amazon_music_oauth_state",
+        }
+    }
+}
+
+/// Encrypted on-disk store for session and platform tokens, kept out of the webview
+pub struct CredentialVault {
+    /// Where the entries are persisted, `None` for a vault living in memory only
+    file: Option<VaultFile>,
+    entries: Mutex<HashMap<String, String>>,
+}
+
+struct VaultFile {
+    path: PathBuf,
+    cipher: ChaCha20Poly1305,
+}
+
+impl CredentialVault {
+    pub fn open(app_handle: &AppHandle) -> AppResult<Self> {
+        let dir = app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| AppError::Internal(format!("No app data directory: {}", e).into()))?;
+        fs::create_dir_all(&dir)?;
+
+        let key = device_key::load_or_create(&dir)?;
+        let cipher = ChaCha20Poly1305::new(&key);
+        let path = dir.join(VAULT_FILE);
+        let entries = Self::read_entries(&path, &cipher).unwrap_or_else(|e| {
+            // A vault we can't decrypt only costs the user a new login
+            warn!("Discarding unreadable credential vault: {}", e);
+            HashMap::new()
+        });
+
+        Ok(Self {
+            file: Some(VaultFile { path, cipher }),
+            entries: Mutex::new(entries),
+        })
+    }
+
+    /// Vault that forgets its secrets when the app quits, for devices where `open` fails
+    pub fn in_memory() -> Self {
+        Self {
+            file: None,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn store(&self, key: CredentialKey, secret: &str) -> AppResult<()> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(key.name().to_string(), secret.to_string());
+        self.write_entries(&entries)
+    }
+
+    pub fn load(&self, key: CredentialKey) -> Option<String> {
+        self.entries.lock().unwrap().get(key.name()).cloned()
+    }
+
+    pub fn clear(&self, key: CredentialKey) -> AppResult<()> {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.remove(key.name()).is_some() {
+            self.write_entries(&entries)?;
+        }
+        Ok(())
+    }
+
+    /// Forget every stored secret
+    pub fn clear_all(&self) -> AppResult<()> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.clear();
+        self.write_entries(&entries)
+    }
+
+    fn read_entries(path: &Path, cipher: &ChaCha20Poly1305) -> AppResult<HashMap<String, String>> {
+        if !path.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let data = fs::read(path)?;
+        if data.len() < NONCE_LEN {
+            return Err(AppError::Internal("Credential vault is truncated".into()));
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| AppError::Internal("Failed to decrypt credential vault".into()))?;
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
+    fn write_entries(&self, entries: &HashMap<String, String>) -> AppResult<()> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+
+        let plaintext = serde_json::to_vec(entries)?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = file
+            .cipher
+            .encrypt(&nonce, plaintext.as_slice())
+            .map_err(|_| AppError::Internal("Failed to encrypt credential vault".into()))?;
+
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&ciphertext);
+
+        // Write then rename so a crash never leaves a half-written vault
+        let tmp_path = file.path.with_extension("tmp");
+        write_private(&tmp_path, &data)?;
+        fs::rename(&tmp_path, &file.path)?;
+        info!("Credential vault saved");
+        Ok(())
+    }
+}
+
+fn write_private(path: &Path, data: &[u8]) -> AppResult<()> {
+    fs::write(path, data)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
+
+/// Vault key kept in the OS keychain where one is available
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "windows"))]
+mod device_key {
+    use super::*;
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use keyring::Entry;
+
+    const SERVICE: &str = "com.swaptun.app";
+    const ACCOUNT: &str = "credential-vault-key";
+
+    pub fn load_or_create(_dir: &Path) -> AppResult<Key> {
+        let entry = Entry::new(SERVICE, ACCOUNT).map_err(keyring_error)?;
+        match entry.get_password() {
+            Ok(encoded) => {
+                let bytes = STANDARD
+                    .decode(encoded)
+                    .map_err(|e| AppError::Internal(format!("Invalid vault key: {}", e).into()))?;
+                if bytes.len() != 32 {
+                    return Err(AppError::Internal("Invalid vault key length".into()));
+                }
+                Ok(*Key::from_slice(&bytes))
+            }
+            Err(keyring::Error::NoEntry) => {
+                let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+                entry
+                    .set_password(&STANDARD.encode(key))
+                    .map_err(keyring_error)?;
+                info!("Created credential vault key in the keychain");
+                Ok(key)
+            }
+            Err(e) => Err(keyring_error(e)),
+        }
+    }
+
+    fn keyring_error(e: keyring::Error) -> AppError {
+        AppError::Internal(format!("Keychain error: {}", e).into())
+    }
+}
+
+/// Vault key wrapped by a non-exportable Android Keystore key, so a copy of the app data
+/// directory alone can't decrypt the vault
+#[cfg(target_os = "android")]
+mod device_key {
+    use super::*;
+    use jni::objects::{JByteArray, JObject, JObjectArray, JValue};
+    use jni::{JNIEnv, JavaVM};
+
+    const WRAPPED_KEY_FILE: &str = "credentials.key.wrapped";
+    const KEYSTORE: &str = "AndroidKeyStore";
+    const ALIAS: &str = "swaptun-credential-vault";
+    const TRANSFORMATION: &str = "AES/GCM/NoPadding";
+    const GCM_IV_LEN: usize = 12;
+    const GCM_TAG_BITS: i32 = 128;
+    // Cipher.ENCRYPT_MODE and Cipher.DECRYPT_MODE
+    const ENCRYPT_MODE: i32 = 1;
+    const DECRYPT_MODE: i32 = 2;
+    // KeyProperties.PURPOSE_ENCRYPT | KeyProperties.PURPOSE_DECRYPT
+    const PURPOSE_ENCRYPT_DECRYPT: i32 = 3;
+
+    type JniResult<T> = jni::errors::Result<T>;
+
+    pub fn load_or_create(dir: &Path) -> AppResult<Key> {
+        let context = ndk_context::android_context();
+        let vm = unsafe { JavaVM::from_raw(context.vm().cast()) }
+            .map_err(|e| AppError::Internal(format!("No Java VM: {}", e).into()))?;
+        let mut env = vm.attach_current_thread().map_err(|e| {
+            AppError::Internal(format!("Failed to attach to the JVM: {}", e).into())
+        })?;
+
+        let path = dir.join(WRAPPED_KEY_FILE);
+        match fs::read(&path) {
+            Ok(wrapped) => {
+                if wrapped.len() <= GCM_IV_LEN {
+                    return Err(AppError::Internal("Invalid wrapped vault key".into()));
+                }
+                let (iv, ciphertext) = wrapped.split_at(GCM_IV_LEN);
+                let bytes = unwrap_key(&mut env, iv, ciphertext)
+                    .map_err(|e| keystore_error(&mut env, e))?;
+                if bytes.len() != 32 {
+                    return Err(AppError::Internal("Invalid vault key length".into()));
+                }
+                Ok(*Key::from_slice(&bytes))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+                let wrapped = wrap_key(&mut env, &key).map_err(|e| keystore_error(&mut env, e))?;
+                write_private(&path, &wrapped)?;
+                info!("Created credential vault key in the Android Keystore");
+                Ok(key)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Encrypt the vault key, returning the IV chosen by the Keystore followed by the ciphertext
+    fn wrap_key(env: &mut JNIEnv, key: &[u8]) -> JniResult<Vec<u8>> {
+        let secret = keystore_secret(env)?;
+        let cipher = init_cipher(env, ENCRYPT_MODE, &secret, None)?;
+        let iv = env.call_method(&cipher, "getIV", "()[B", &[])?.l()?;
+        let mut wrapped = env.convert_byte_array(JByteArray::from(iv))?;
+        wrapped.extend(do_final(env, &cipher, key)?);
+        Ok(wrapped)
+    }
+
+    fn unwrap_key(env: &mut JNIEnv, iv: &[u8], ciphertext: &[u8]) -> JniResult<Vec<u8>> {
+        let secret = keystore_secret(env)?;
+        let cipher = init_cipher(env, DECRYPT_MODE, &secret, Some(iv))?;
+        do_final(env, &cipher, ciphertext)
+    }
+
+    /// AES key of the app in the Android Keystore, generated on first use
+    fn keystore_secret<'local>(env: &mut JNIEnv<'local>) -> JniResult<JObject<'local>> {
+        let provider = env.new_string(KEYSTORE)?;
+        let alias = env.new_string(ALIAS)?;
+
+        let keystore = env
+            .call_static_method(
+                "java/security/KeyStore",
+                "getInstance",
+                "(Ljava/lang/String;)Ljava/security/KeyStore;",
+                &[JValue::Object(&provider)],
+            )?
+            .l()?;
+        env.call_method(
+            &keystore,
+            "load",
+            "(Ljava/security/KeyStore$LoadStoreParameter;)V",
+            &[JValue::Object(&JObject::null())],
+        )?;
+        let secret = env
+            .call_method(
+                &keystore,
+                "getKey",
+                "(Ljava/lang/String;[C)Ljava/security/Key;",
+                &[JValue::Object(&alias), JValue::Object(&JObject::null())],
+            )?
+            .l()?;
+        if !secret.is_null() {
+            return Ok(secret);
+        }
+
+        let builder = env.new_object(
+            "android/security/keystore/KeyGenParameterSpec$Builder",
+            "(Ljava/lang/String;I)V",
+            &[JValue::Object(&alias), JValue::Int(PURPOSE_ENCRYPT_DECRYPT)],
+        )?;
+        let block_modes = string_array(env, "GCM")?;
+        let paddings = string_array(env, "NoPadding")?;
+        for (setter, values) in [
+            ("setBlockModes", &block_modes),
+            ("setEncryptionPaddings", &paddings),
+        ] {
+            env.call_method(
+                &builder,
+                setter,
+                "([Ljava/lang/String;)Landroid/security/keystore/KeyGenParameterSpec$Builder;",
+                &[JValue::Object(values)],
+            )?;
+        }
+        env.call_method(
+            &builder,
+            "setKeySize",
+            "(I)Landroid/security/keystore/KeyGenParameterSpec$Builder;",
+            &[JValue::Int(256)],
+        )?;
+        let spec = env
+            .call_method(
+                &builder,
+                "build",
+                "()Landroid/security/keystore/KeyGenParameterSpec;",
+                &[],
+            )?
+            .l()?;
+
+        let algorithm = env.new_string("AES")?;
+        let generator = env
+            .call_static_method(
+                "javax/crypto/KeyGenerator",
+                "getInstance",
+                "(Ljava/lang/String;Ljava/lang/String;)Ljavax/crypto/KeyGenerator;",
+                &[JValue::Object(&algorithm), JValue::Object(&provider)],
+            )?
+            .l()?;
+        env.call_method(
+            &generator,
+            "init",
+            "(Ljava/security/spec/AlgorithmParameterSpec;)V",
+            &[JValue::Object(&spec)],
+        )?;
+        env.call_method(&generator, "generateKey", "()Ljavax/crypto/SecretKey;", &[])?
+            .l()
+    }
+
+    fn init_cipher<'local>(
+        env: &mut JNIEnv<'local>,
+        mode: i32,
+        secret: &JObject,
+        iv: Option<&[u8]>,
+    ) -> JniResult<JObject<'local>> {
+        let transformation = env.new_string(TRANSFORMATION)?;
+        let cipher = env
+            .call_static_method(
+                "javax/crypto/Cipher",
+                "getInstance",
+                "(Ljava/lang/String;)Ljavax/crypto/Cipher;",
+                &[JValue::Object(&transformation)],
+            )?
+            .l()?;
+
+        match iv {
+            // The Keystore picks a random IV itself when encrypting
+            None => env.call_method(
+                &cipher,
+                "init",
+                "(ILjava/security/Key;)V",
+                &[JValue::Int(mode), JValue::Object(secret)],
+            )?,
+            Some(iv) => {
+                let iv = env.byte_array_from_slice(iv)?;
+                let spec = env.new_object(
+                    "javax/crypto/spec/GCMParameterSpec",
+                    "(I[B)V",
+                    &[JValue::Int(GCM_TAG_BITS), JValue::Object(&iv)],
+                )?;
+                env.call_method(
+                    &cipher,
+                    "init",
+                    "(ILjava/security/Key;Ljava/security/spec/AlgorithmParameterSpec;)V",
+                    &[
+                        JValue::Int(mode),
+                        JValue::Object(secret),
+                        JValue::Object(&spec),
+                    ],
+                )?
+            }
+        };
+        Ok(cipher)
+    }
+
+    fn do_final(env: &mut JNIEnv, cipher: &JObject, input: &[u8]) -> JniResult<Vec<u8>> {
+        let input = env.byte_array_from_slice(input)?;
+        let output = env
+            .call_method(cipher, "doFinal", "([B)[B", &[JValue::Object(&input)])?
+            .l()?;
+        env.convert_byte_array(JByteArray::from(output))
+    }
+
+    fn string_array<'local>(
+        env: &mut JNIEnv<'local>,
+        value: &str,
+    ) -> JniResult<JObjectArray<'local>> {
+        let value = env.new_string(value)?;
+        env.new_object_array(1, "java/lang/String", &value)
+    }
+
+    /// Clear the Java exception behind a failed call so the thread can keep using the JVM
+    fn keystore_error(env: &mut JNIEnv, e: jni::errors::Error) -> AppError {
+        if env.exception_check().unwrap_or(false) {
+            let _ = env.exception_describe();
+            let _ = env.exception_clear();
+        }
+        AppError::Internal(format!("Android Keystore error: {}", e).into())
+    }
+}
+
+/// Vault key kept in a private file of the app data directory, the fallback for Linux and tests
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "windows",
+    target_os = "android"
+)))]
+mod device_key {
+    use super::*;
+
+    const KEY_FILE: &str = "credentials.key";
+
+    pub fn load_or_create(dir: &Path) -> AppResult<Key> {
+        let path = dir.join(KEY_FILE);
+        match fs::read(&path) {
+            Ok(bytes) if bytes.len() == 32 => Ok(*Key::from_slice(&bytes)),
+            Ok(_) => Err(AppError::Internal("Invalid vault key file".into())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+                write_private(&path, &key)?;
+                info!("Created credential vault key file");
+                Ok(key)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
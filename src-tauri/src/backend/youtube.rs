@@ -1,35 +1,75 @@
-use crate::backend::backend::BackendClient;
+use crate::backend::backend::RequestOptions;
+use crate::backend::oauth::PlatformAuthFailure;
+use crate::backend::platform::{MusicPlatform, PlatformContext, PlatformStatus};
 use crate::error::AppResult;
-use tauri::{http::StatusCode, AppHandle};
-
-use swaptun_backend::{AddTokenRequest, YoutubeUrlResponse};
+use async_trait::async_trait;
+use swaptun_backend::{GetPlaylistResponse, PlaylistOrigin, YoutubeUrlResponse};
+use tauri::http::StatusCode;
+use tauri::Url;
 
 pub struct YoutubeClient {
-    backend_client: BackendClient,
+    context: PlatformContext,
 }
 
 impl YoutubeClient {
-    pub fn new(app_handle: AppHandle) -> Self {
-        Self {
-            backend_client: BackendClient::new(app_handle),
-        }
+    pub fn new(context: PlatformContext) -> Self {
+        Self { context }
     }
 
     pub async fn get_auth_url(&self) -> AppResult<YoutubeUrlResponse> {
-        self.backend_client
+        self.context
+            .backend_client
             .get::<YoutubeUrlResponse>("youtube/authorization-url")
             .await
     }
+}
+
+#[async_trait]
+impl MusicPlatform for YoutubeClient {
+    fn origin(&self) -> PlaylistOrigin {
+        PlaylistOrigin::YoutubeMusic
+    }
 
-    pub async fn add_token(&self, req: AddTokenRequest) -> AppResult<StatusCode> {
-        self.backend_client
-            .post("youtube/token", serde_json::to_string(&req).unwrap())
+    async fn connect(&self, _request_id: Option<String>) -> AppResult<()> {
+        let response = self.get_auth_url().await?;
+        self.context
+            .open_authorization(PlaylistOrigin::YoutubeMusic, &response.url)
+    }
+
+    async fn handle_callback(&self, callback: &Url) -> Result<(), PlatformAuthFailure> {
+        self.context
+            .exchange_code(PlaylistOrigin::YoutubeMusic, callback, "youtube/token")
             .await
     }
 
-    pub async fn disconnect(&self) -> AppResult<StatusCode> {
-        self.backend_client
+    async fn import_playlists(&self, request_id: Option<String>) -> AppResult<StatusCode> {
+        self.context
+            .backend_client
+            .post_json_with_options(
+                "youtube/playlist",
+                &(),
+                RequestOptions::long_running(request_id),
+            )
+            .await
+    }
+
+    async fn list_playlists(&self) -> AppResult<GetPlaylistResponse> {
+        self.context
+            .list_playlists(PlaylistOrigin::YoutubeMusic)
+            .await
+    }
+
+    async fn disconnect(&self) -> AppResult<StatusCode> {
+        self.context
+            .backend_client
             .delete("youtube/disconnect")
             .await
     }
+
+    async fn status(&self) -> AppResult<PlatformStatus> {
+        self.context
+            .backend_client
+            .get_with_options("youtube/status", RequestOptions::quiet())
+            .await
+    }
 }
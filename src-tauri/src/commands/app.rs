@@ -1,21 +1,64 @@
 use std::sync::Arc;
 
 use log::error;
-use swaptun_backend::{
-    AddFriendRequest, GetUsersRequest, RemoveFriendRequest, SearchField, UserBean,
-};
-use tauri::{command, AppHandle, State};
+use swaptun_backend::{GetUsersRequest, RemoveFriendRequest, SearchField, UserBean};
+use tauri::{command, AppHandle, Emitter, State};
 use tauri_plugin_deep_link::DeepLinkExt;
 use tauri_plugin_opener::OpenerExt;
 
 use crate::app::App;
+use crate::backend::{
+    BackendEnvironment, ConnectivityStatus, FriendInvite, FriendRequest, FriendRequestDirection,
+    ReportReason, ReportUserRequest, UserPage,
+};
+use crate::error::{AppError, AppResult};
+use crate::handle_result;
+use crate::utils::status_to_result;
+
+#[command]
+pub async fn is_app_ready(app: State<'_, Arc<App>>) -> AppResult<bool> {
+    Ok(app.is_app_ready().await)
+}
+
+/// Called by the frontend once it can route, releasing the queued deep links
+#[command]
+pub async fn set_app_ready(app: State<'_, Arc<App>>) -> AppResult<()> {
+    app.set_app_ready().await;
+    handle_result!(
+        app.app_handle().emit("app_ready", "").map_err(|e| {
+            AppError::Internal(format!("Failed to emit app_ready event: {}", e).into())
+        }),
+        "Failed to mark app as ready"
+    )
+}
+#[command]
+pub async fn get_connectivity_status(app: State<'_, Arc<App>>) -> AppResult<ConnectivityStatus> {
+    Ok(app.get_connectivity_status())
+}
 
 #[command]
-pub async fn is_app_ready(app: State<'_, Arc<App>>) -> Result<bool, String> {
-    app.is_app_ready().await
+pub async fn cancel_request(app: State<'_, Arc<App>>, request_id: String) -> AppResult<bool> {
+    Ok(app.cancel_request(&request_id))
 }
+
+#[command]
+pub async fn get_backend_environment(app: State<'_, Arc<App>>) -> AppResult<BackendEnvironment> {
+    Ok(app.get_backend_environment())
+}
+
+#[command]
+pub async fn set_backend_environment(
+    app: State<'_, Arc<App>>,
+    environment: BackendEnvironment,
+) -> AppResult<()> {
+    handle_result!(
+        app.set_backend_environment(environment),
+        "Failed to set backend environment"
+    )
+}
+
 #[command]
-pub async fn check_opening_url(app: State<'_, Arc<App>>) -> Result<(), String> {
+pub async fn check_opening_url(app: State<'_, Arc<App>>) -> AppResult<()> {
     let app_handle = app.app_handle();
     let current_route = match app_handle.deep_link().get_current() {
         Ok(urls) => urls,
@@ -30,75 +73,165 @@ pub async fn check_opening_url(app: State<'_, Arc<App>>) -> Result<(), String> {
     Ok(())
 }
 
+/// One page of users matching `search` on `field` (username by default)
+///
+/// Pass the previous page's `next_cursor` to get the next one. Only the latest
+/// query gets results, the ones it supersedes fail with the `cancelled` code.
 #[command]
 pub async fn search_users(
     app: State<'_, Arc<App>>,
     search: Option<String>,
-) -> Result<Vec<UserBean>, String> {
+    field: Option<SearchField>,
+    cursor: Option<String>,
+) -> AppResult<UserPage> {
     let request = GetUsersRequest {
         include_deleted: Some(false),
         search,
-        search_field: Some(SearchField::Username),
-        limit: Some(100),
+        search_field: Some(field.unwrap_or(SearchField::Username)),
+        limit: None,
         offset: None,
         friends_priority: false,
         exclude_friends: false,
         exclude_self: Some(true),
     };
 
-    match app.search_users(request).await {
-        Ok(users) => Ok(users),
-        Err(e) => Err(format!("Error fetching users: {}", e)),
-    }
+    handle_result!(
+        app.search_users(request, cursor).await,
+        "Error fetching users"
+    )
 }
 
 #[command]
-pub async fn get_friends(app: State<'_, Arc<App>>) -> Result<Vec<UserBean>, String> {
-    match app.get_friends().await {
-        Ok(friends) => Ok(friends),
-        Err(e) => Err(format!("Error fetching friends: {}", e)),
-    }
+pub async fn get_friends(app: State<'_, Arc<App>>) -> AppResult<Vec<UserBean>> {
+    handle_result!(app.get_friends().await, "Error fetching friends")
 }
 
+/// Block a user, they disappear from searches and shared playlists and stop being a friend
 #[command]
-pub async fn add_friend(app: State<'_, Arc<App>>, request: AddFriendRequest) -> Result<(), String> {
-    match app.add_friend(request).await {
-        Ok(_) => Ok(()),
-        Err(e) => Err(format!("Error adding friend: {}", e)),
-    }
+pub async fn block_user(app: State<'_, Arc<App>>, user_id: i32) -> AppResult<bool> {
+    let status = handle_result!(app.block_user(user_id).await, "Error blocking user")?;
+    status_to_result(status, "Block user")
+}
+
+#[command]
+pub async fn unblock_user(app: State<'_, Arc<App>>, user_id: i32) -> AppResult<bool> {
+    let status = handle_result!(app.unblock_user(user_id).await, "Error unblocking user")?;
+    status_to_result(status, "Unblock user")
+}
+
+#[command]
+pub async fn get_blocked_users(app: State<'_, Arc<App>>) -> AppResult<Vec<UserBean>> {
+    handle_result!(
+        app.get_blocked_users().await,
+        "Error fetching blocked users"
+    )
+}
+
+#[command]
+pub async fn report_user(
+    app: State<'_, Arc<App>>,
+    user_id: i32,
+    reason: ReportReason,
+    details: Option<String>,
+) -> AppResult<bool> {
+    let request = ReportUserRequest { reason, details };
+    let status = handle_result!(
+        app.report_user(user_id, request).await,
+        "Error reporting user"
+    )?;
+    status_to_result(status, "Report user")
+}
+
+/// Personal invite link and QR code, anyone opening it sends the user a friend request
+#[command]
+pub async fn create_friend_invite(app: State<'_, Arc<App>>) -> AppResult<FriendInvite> {
+    handle_result!(
+        app.create_friend_invite().await,
+        "Error creating friend invite"
+    )
+}
+
+#[command]
+pub async fn send_friend_request(
+    app: State<'_, Arc<App>>,
+    user_id: i32,
+) -> AppResult<FriendRequest> {
+    handle_result!(
+        app.send_friend_request(user_id).await,
+        "Error sending friend request"
+    )
+}
+
+#[command]
+pub async fn get_friend_requests(
+    app: State<'_, Arc<App>>,
+    direction: FriendRequestDirection,
+) -> AppResult<Vec<FriendRequest>> {
+    handle_result!(
+        app.get_friend_requests(direction).await,
+        "Error fetching friend requests"
+    )
+}
+
+#[command]
+pub async fn accept_friend_request(
+    app: State<'_, Arc<App>>,
+    request_id: i32,
+) -> AppResult<UserBean> {
+    handle_result!(
+        app.accept_friend_request(request_id).await,
+        "Error accepting friend request"
+    )
+}
+
+#[command]
+pub async fn decline_friend_request(app: State<'_, Arc<App>>, request_id: i32) -> AppResult<bool> {
+    let status = handle_result!(
+        app.decline_friend_request(request_id).await,
+        "Error declining friend request"
+    )?;
+    status_to_result(status, "Decline friend request")
+}
+
+#[command]
+pub async fn cancel_friend_request(app: State<'_, Arc<App>>, request_id: i32) -> AppResult<bool> {
+    let status = handle_result!(
+        app.cancel_friend_request(request_id).await,
+        "Error cancelling friend request"
+    )?;
+    status_to_result(status, "Cancel friend request")
 }
 
 #[command]
 pub async fn remove_friend(
     app: State<'_, Arc<App>>,
     request: RemoveFriendRequest,
-) -> Result<(), String> {
-    match app.remove_friend(request).await {
-        Ok(_) => Ok(()),
-        Err(e) => Err(format!("Error removing friend: {}", e)),
-    }
+) -> AppResult<()> {
+    handle_result!(app.remove_friend(request).await, "Error removing friend")
 }
 
 #[command]
 pub async fn search_non_friends_users(
     app: State<'_, Arc<App>>,
     search: Option<String>,
-) -> Result<Vec<UserBean>, String> {
+    field: Option<SearchField>,
+    cursor: Option<String>,
+) -> AppResult<UserPage> {
     let request = GetUsersRequest {
         include_deleted: Some(false),
         search,
-        search_field: Some(SearchField::Username),
-        limit: Some(100),
+        search_field: Some(field.unwrap_or(SearchField::Username)),
+        limit: None,
         offset: None,
         friends_priority: true,
         exclude_friends: true,
         exclude_self: Some(true),
     };
 
-    match app.search_users(request).await {
-        Ok(users) => Ok(users),
-        Err(e) => Err(format!("Error fetching users: {}", e)),
-    }
+    handle_result!(
+        app.search_users(request, cursor).await,
+        "Error fetching users"
+    )
 }
 
 #[command]
@@ -106,7 +239,7 @@ pub async fn open_external_app(
     app_handle: AppHandle,
     platform: String,
     playlist_id: Option<String>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let url = match platform.as_str() {
         "Spotify" => {
             if let Some(id) = playlist_id {
@@ -159,14 +292,43 @@ pub async fn open_external_app(
                 "deezer://".to_string()
             }
         }
-        _ => return Err(format!("Unknown platform: {}", platform)),
+        "Tidal" => {
+            if let Some(id) = playlist_id {
+                // Tidal uses: tidal://playlist/{id}
+                format!("tidal://playlist/{}", id)
+            } else {
+                "tidal://".to_string()
+            }
+        }
+        "SoundCloud" => {
+            if let Some(id) = playlist_id {
+                // SoundCloud uses: soundcloud://playlists/{id}
+                format!("soundcloud://playlists/{}", id)
+            } else {
+                "soundcloud://".to_string()
+            }
+        }
+        "AmazonMusic" => {
+            if let Some(id) = playlist_id {
+                // Amazon Music uses: amznmp3://playlists/{id}
+                format!("amznmp3://playlists/{}", id)
+            } else {
+                "amznmp3://".to_string()
+            }
+        }
+        _ => {
+            return Err(AppError::Validation(
+                format!("Unknown platform: {}", platform).into(),
+            ))
+        }
     };
 
     // Use opener plugin which works cross-platform
-    if let Err(e) = app_handle.opener().open_url(url.clone(), None::<&str>) {
-        error!("Failed to open external app: {}", e);
-        return Err(format!("Failed to open {}: {}", platform, e));
-    }
-
-    Ok(())
+    handle_result!(
+        app_handle
+            .opener()
+            .open_url(url.clone(), None::<&str>)
+            .map_err(|e| AppError::Internal(format!("Failed to open {}: {}", platform, e).into())),
+        "Failed to open external app"
+    )
 }
@@ -1,13 +1,17 @@
 use std::sync::Arc;
 
-use log::{error, info};
+use log::info;
 use swaptun_backend::{
-    CreateUserRequest, ForgotPasswordRequest, LoginEmailRequest, LoginRequest, LoginResponse,
+    CreateUserRequest, ForgotPasswordRequest, LoginEmailRequest, LoginRequest,
     ResetPasswordRequest, VerifyTokenRequest,
 };
-use tauri::{command, Emitter, State};
+use tauri::{command, State};
 
 use crate::app::App;
+use crate::backend::AuthenticatedUser;
+use crate::error::AppResult;
+use crate::handle_result;
+use crate::utils::status_to_result;
 
 #[command]
 pub async fn register(
@@ -17,7 +21,7 @@ pub async fn register(
     first_name: &str,
     last_name: &str,
     email: &str,
-) -> Result<bool, String> {
+) -> AppResult<bool> {
     let request = CreateUserRequest {
         username: username.to_string(),
         password: password.to_string(),
@@ -27,87 +31,54 @@ pub async fn register(
     };
 
     // ICI on peut accèder aux éléments de l'App
-    match app.register(request).await {
-        Ok(response) => {
-            if response.is_success() {
-                return Ok(true);
-            } else {
-                return Err("Failed to register".to_string());
-            }
-        }
-        Err(e) => {
-            return Err(format!("Error: {}", e));
-        }
-    }
+    let status = handle_result!(app.register(request).await, "Failed to register")?;
+    status_to_result(status, "Register")
 }
 
 #[command]
-pub async fn logout(app: State<'_, Arc<App>>) -> Result<(), String> {
-    // Emit an event to trigger the logout in the frontend
-    app.app_handle()
-        .emit("logout", "")
-        .map_err(|e| format!("Failed to emit logout event: {}", e))?;
-    Ok(())
+pub async fn logout(app: State<'_, Arc<App>>) -> AppResult<()> {
+    handle_result!(app.logout().await, "Failed to log out")
 }
 
 #[command]
 pub async fn login(
     app: State<'_, Arc<App>>,
     request: LoginRequest,
-) -> Result<LoginResponse, String> {
+) -> AppResult<AuthenticatedUser> {
     // ICI on peut accèder aux éléments de l'App
-    match app.login(request).await {
-        Ok(response) => Ok(response),
-        Err(e) => Err(e.to_string()),
-    }
+    handle_result!(app.login(request).await, "Failed to log in")
 }
 
 #[command]
 pub async fn login_email(
     app: State<'_, Arc<App>>,
     request: LoginEmailRequest,
-) -> Result<LoginResponse, String> {
+) -> AppResult<AuthenticatedUser> {
     // ICI on peut accèder aux éléments de l'App
-    match app.login_email(request).await {
-        Ok(response) => Ok(response),
-        Err(e) => Err(e.to_string()),
-    }
+    handle_result!(app.login_email(request).await, "Failed to log in")
 }
 
 #[command]
-pub async fn verify_token(app: State<'_, Arc<App>>, token: String) -> Result<bool, String> {
+pub async fn verify_token(app: State<'_, Arc<App>>, token: String) -> AppResult<bool> {
     let request = VerifyTokenRequest {
         token: token.clone(),
     };
-    match app.verify_token(request).await {
-        Ok(is_valid) => Ok(is_valid.valid),
-        Err(e) => Err(e.to_string()),
-    }
+    let response = handle_result!(app.verify_token(request).await, "Failed to verify token")?;
+    Ok(response.valid)
 }
 
 #[command]
 pub async fn forgot_password(
     app: State<'_, Arc<App>>,
     request: ForgotPasswordRequest,
-) -> Result<bool, String> {
-    match app.forgot_password(request).await {
-        Ok(status) => {
-            if status.is_success() {
-                info!("Forgot password request sent successfully");
-                Ok(true)
-            } else {
-                error!("Failed to send forgot password request, status: {}", status);
-                Err(format!(
-                    "Failed to send forgot password request, status: {}",
-                    status
-                ))
-            }
-        }
-        Err(e) => {
-            error!("Failed to send forgot password request: {}", e);
-            Err(e.to_string())
-        }
-    }
+) -> AppResult<bool> {
+    let status = handle_result!(
+        app.forgot_password(request).await,
+        "Failed to send forgot password request"
+    )?;
+    status_to_result(status, "Forgot password request")?;
+    info!("Forgot password request sent successfully");
+    Ok(true)
 }
 
 #[command]
@@ -115,20 +86,11 @@ pub async fn reset_password(
     app: State<'_, Arc<App>>,
     token: String,
     request: ResetPasswordRequest,
-) -> Result<bool, String> {
-    info!("Reset password request: {:?}", request);
-    match app.reset_password(request, token).await {
-        Ok(status) => {
-            if status.is_success() {
-                Ok(true)
-            } else {
-                error!("Failed to reset password, status: {}", status);
-                Err(format!("Failed to reset password, status: {}", status))
-            }
-        }
-        Err(e) => {
-            error!("Failed to reset password: {}", e);
-            Err(e.to_string())
-        }
-    }
+) -> AppResult<bool> {
+    info!("Reset password request received");
+    let status = handle_result!(
+        app.reset_password(request, token).await,
+        "Failed to reset password"
+    )?;
+    status_to_result(status, "Reset password")
 }
@@ -1,16 +1,10 @@
 pub mod app;
-pub mod apple;
 pub mod auth;
-pub mod deezer;
 pub mod notifications;
+pub mod platforms;
 pub mod playlists;
-pub mod spotify;
-pub mod youtube;
 pub use app::*;
-pub use apple::*;
 pub use auth::*;
-pub use deezer::*;
 pub use notifications::*;
+pub use platforms::*;
 pub use playlists::*;
-pub use spotify::*;
-pub use youtube::*;
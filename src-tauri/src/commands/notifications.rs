@@ -5,6 +5,9 @@ use swaptun_backend::{RegisterFcmTokenRequest, SendTestNotificationRequest};
 use tauri::{command, AppHandle, Emitter, State};
 use tauri_plugin_push_notifications::PushNotificationsExt;
 
+use crate::error::AppResult;
+use crate::handle_result;
+use crate::utils::status_to_result;
 use crate::{app::App, models::Notification};
 
 /// Handle notification data by emitting appropriate events
@@ -15,6 +18,11 @@ pub fn handle_notification_data(app: &AppHandle, data: Notification) {
             error!("Failed to emit playlist_shared event: {}", e);
         }
     }
+    if let Some(friend_request) = data.friend_request {
+        if let Err(e) = app.emit("friend_request_received", friend_request) {
+            error!("Failed to emit friend_request_received event: {}", e);
+        }
+    }
 }
 
 /// Handle notification click (when app is closed and user clicks notification)
@@ -30,26 +38,21 @@ pub fn handle_notification(app: &AppHandle, data: Notification) {
 }
 
 #[command]
-pub async fn set_fcm_token(app: State<'_, Arc<App>>, token: String) -> Result<bool, String> {
+pub async fn set_fcm_token(app: State<'_, Arc<App>>, token: String) -> AppResult<bool> {
     let register_fcm_token_request = RegisterFcmTokenRequest {
         token,
         device_id: Some("default_device_id".to_string()), // Replace with actual device id if available
         platform: Some("default_platform".to_string()), // Replace with actual platform if available
     };
-    match app.set_fcm_token(register_fcm_token_request).await {
-        Ok(status) => {
-            if status.is_success() {
-                Ok(true)
-            } else {
-                Err(format!("Failed to set FCM token, status: {}", status))
-            }
-        }
-        Err(e) => Err(e.to_string()),
-    }
+    let status = handle_result!(
+        app.set_fcm_token(register_fcm_token_request).await,
+        "Failed to set FCM token"
+    )?;
+    status_to_result(status, "Set FCM token")
 }
 
 #[command]
-pub async fn check_opening_notification(app: State<'_, Arc<App>>) -> Result<(), String> {
+pub async fn check_opening_notification(app: State<'_, Arc<App>>) -> AppResult<()> {
     let app_handle = app.app_handle().clone();
     match app_handle
         .push_notifications()
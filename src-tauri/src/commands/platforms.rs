@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use swaptun_backend::{GetPlaylistResponse, PlaylistOrigin};
+use tauri::{command, State};
+
+use crate::app::App;
+use crate::backend::PlatformConnection;
+use crate::error::AppResult;
+use crate::handle_result;
+use crate::utils::status_to_result;
+
+/// Start linking a streaming platform account, the result arrives through events
+#[command]
+pub async fn connect_platform(
+    app: State<'_, Arc<App>>,
+    origin: PlaylistOrigin,
+    request_id: Option<String>,
+) -> AppResult<()> {
+    handle_result!(
+        app.connect_platform(origin, request_id).await,
+        "Failed to connect platform"
+    )
+}
+
+#[command]
+pub async fn import_playlists(
+    app: State<'_, Arc<App>>,
+    origin: PlaylistOrigin,
+    request_id: Option<String>,
+) -> AppResult<bool> {
+    let status = handle_result!(
+        app.import_platform_playlists(origin, request_id).await,
+        "Failed to import playlists"
+    )?;
+    status_to_result(status, "Import playlists")
+}
+
+#[command]
+pub async fn get_playlists(
+    app: State<'_, Arc<App>>,
+    origin: PlaylistOrigin,
+) -> AppResult<GetPlaylistResponse> {
+    handle_result!(app.get_playlists(origin).await, "Failed to get playlists")
+}
+
+#[command]
+pub async fn disconnect_platform(
+    app: State<'_, Arc<App>>,
+    origin: PlaylistOrigin,
+) -> AppResult<bool> {
+    let status = handle_result!(
+        app.disconnect_platform(origin).await,
+        "Failed to disconnect platform"
+    )?;
+    status_to_result(status, "Disconnect platform")
+}
+
+/// Connection state of every supported platform
+#[command]
+pub async fn get_platform_connections(
+    app: State<'_, Arc<App>>,
+) -> AppResult<Vec<PlatformConnection>> {
+    handle_result!(
+        app.get_platform_connections().await,
+        "Failed to get platform connections"
+    )
+}
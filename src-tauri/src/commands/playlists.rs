@@ -1,10 +1,12 @@
 use std::sync::Arc;
 
 use crate::app::App;
+use crate::backend::{CreateShareLinkRequest, ShareLink, ShareLinkVisibility};
+use crate::error::AppResult;
+use crate::handle_result;
+use crate::utils::status_to_result;
 
-use log::error;
 use swaptun_backend::GetPlaylistMusicsResponse;
-use swaptun_backend::GetPlaylistResponse;
 use swaptun_backend::SendPlaylistRequest;
 use swaptun_backend::SendPlaylistResponse;
 use swaptun_backend::SharedPlaylistsResponse;
@@ -14,11 +16,12 @@ pub async fn send_playlist(
     app: State<'_, Arc<App>>,
     playlist_id: i32,
     req: SendPlaylistRequest,
-) -> Result<SendPlaylistResponse, String> {
-    match app.send_playlist(playlist_id, req).await {
-        Ok(response) => Ok(response),
-        Err(e) => Err(e.to_string()),
-    }
+    request_id: Option<String>,
+) -> AppResult<SendPlaylistResponse> {
+    handle_result!(
+        app.send_playlist(playlist_id, req, request_id).await,
+        "Failed to send playlist"
+    )
 }
 
 #[command]
@@ -26,62 +29,77 @@ pub async fn share_playlist(
     app: State<'_, Arc<App>>,
     playlist_id: i32,
     user_id: i32,
-) -> Result<bool, String> {
-    match app.share_playlist(playlist_id, user_id).await {
-        Ok(status) => {
-            if status.is_success() {
-                Ok(true)
-            } else {
-                Err(format!("Failed to share playlist, status: {}", status))
-            }
-        }
-        Err(e) => Err(e.to_string()),
-    }
+) -> AppResult<bool> {
+    let status = handle_result!(
+        app.share_playlist(playlist_id, user_id).await,
+        "Failed to share playlist"
+    )?;
+    status_to_result(status, "Share playlist")
 }
 
 #[command]
-pub async fn get_shared_playlists(
-    app: State<'_, Arc<App>>,
-) -> Result<SharedPlaylistsResponse, String> {
-    match app.get_shared_playlists().await {
-        Ok(response) => Ok(response),
-        Err(e) => {
-            error!("Failed to get shared playlists: {}", e);
-            Err(e.to_string())
-        }
-    }
+pub async fn get_shared_playlists(app: State<'_, Arc<App>>) -> AppResult<SharedPlaylistsResponse> {
+    handle_result!(
+        app.get_shared_playlists().await,
+        "Failed to get shared playlists"
+    )
 }
 
 #[command]
 pub async fn mark_shared_playlist_viewed(
     app: State<'_, Arc<App>>,
     shared_playlist_id: i32,
-) -> Result<bool, String> {
-    match app.mark_shared_playlist_viewed(shared_playlist_id).await {
-        Ok(status) => {
-            if status.is_success() {
-                Ok(true)
-            } else {
-                Err(format!(
-                    "Failed to mark playlist as viewed, status: {}",
-                    status
-                ))
-            }
-        }
-        Err(e) => Err(e.to_string()),
-    }
+) -> AppResult<bool> {
+    let status = handle_result!(
+        app.mark_shared_playlist_viewed(shared_playlist_id).await,
+        "Failed to mark playlist as viewed"
+    )?;
+    status_to_result(status, "Mark playlist as viewed")
 }
 
 #[command]
 pub async fn get_playlist_musics(
     app: State<'_, Arc<App>>,
     playlist_id: i32,
-) -> Result<GetPlaylistMusicsResponse, String> {
-    match app.get_playlist_musics(playlist_id).await {
-        Ok(response) => Ok(response),
-        Err(e) => {
-            error!("Failed to get playlist musics: {}", e);
-            Err(e.to_string())
-        }
-    }
+) -> AppResult<GetPlaylistMusicsResponse> {
+    handle_result!(
+        app.get_playlist_musics(playlist_id).await,
+        "Failed to get playlist musics"
+    )
+}
+
+#[command]
+pub async fn create_playlist_share_link(
+    app: State<'_, Arc<App>>,
+    playlist_id: i32,
+    visibility: Option<ShareLinkVisibility>,
+    expires_in_hours: Option<u32>,
+) -> AppResult<ShareLink> {
+    let req = CreateShareLinkRequest {
+        visibility: visibility.unwrap_or_default(),
+        expires_in_hours,
+    };
+    handle_result!(
+        app.create_share_link(playlist_id, req).await,
+        "Failed to create playlist share link"
+    )
+}
+
+/// Called by the frontend after login, opening a share link received while logged out
+#[command]
+pub async fn open_pending_share_link(app: State<'_, Arc<App>>) -> AppResult<()> {
+    app.open_pending_share_link().await;
+    Ok(())
+}
+
+#[command]
+pub async fn revoke_playlist_share_link(
+    app: State<'_, Arc<App>>,
+    token: String,
+) -> AppResult<bool> {
+    let status = handle_result!(
+        app.revoke_share_link(&token).await,
+        "Failed to revoke playlist share link"
+    )?;
+    status_to_result(status, "Revoke playlist share link")
 }
@@ -0,0 +1,103 @@
+use serde::Serialize;
+use swaptun_backend::PlaylistOrigin;
+use tauri::Url;
+
+/// Event emitted for deep links the Rust side does not handle itself
+pub const DEEP_LINK_EVENT: &str = "deep_link";
+
+/// Destination of a deep link opened on the device
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "route", rename_all = "snake_case")]
+pub enum DeepLinkRoute {
+    /// OAuth redirect of a streaming platform, never forwarded to the webview
+    #[serde(skip_serializing)]
+    PlatformCallback {
+        platform: PlaylistOrigin,
+        url: Url,
+    },
+    ResetPassword {
+        token: Option<String>,
+    },
+    SharedPlaylist {
+        token: String,
+    },
+    FriendInvite {
+        token: String,
+    },
+    Profile {
+        user_id: i32,
+    },
+    Unknown {
+        url: String,
+    },
+}
+
+/// Turns deep-link URLs into typed routes
+pub struct DeepLinkRouter;
+
+impl DeepLinkRouter {
+    pub fn parse(url: &Url) -> DeepLinkRoute {
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+
+        match segments.as_slice() {
+            ["open", "spotify"] => DeepLinkRoute::PlatformCallback {
+                platform: PlaylistOrigin::Spotify,
+                url: url.clone(),
+            },
+            ["open", "youtube"] => DeepLinkRoute::PlatformCallback {
+                platform: PlaylistOrigin::YoutubeMusic,
+                url: url.clone(),
+            },
+            ["open", "deezer"] => DeepLinkRoute::PlatformCallback {
+                platform: PlaylistOrigin::Deezer,
+                url: url.clone(),
+            },
+            ["open", "tidal"] => DeepLinkRoute::PlatformCallback {
+                platform: PlaylistOrigin::Tidal,
+                url: url.clone(),
+            },
+            ["open", "soundcloud"] => DeepLinkRoute::PlatformCallback {
+                platform: PlaylistOrigin::SoundCloud,
+                url: url.clone(),
+            },
+            ["open", "amazon-music"] => DeepLinkRoute::PlatformCallback {
+                platform: PlaylistOrigin::AmazonMusic,
+                url: url.clone(),
+            },
+            ["reset-password"] => DeepLinkRoute::ResetPassword {
+                token: url
+                    .query_pairs()
+                    .find(|(key, _)| key == "token")
+                    .map(|(_, value)| value.into_owned()),
+            },
+            ["p", token] if Self::is_token(token) => DeepLinkRoute::SharedPlaylist {
+                token: token.to_string(),
+            },
+            ["invite", token] if Self::is_token(token) => DeepLinkRoute::FriendInvite {
+                token: token.to_string(),
+            },
+            ["profile", user_id] | ["u", user_id] => match user_id.parse() {
+                Ok(user_id) => DeepLinkRoute::Profile { user_id },
+                Err(_) => Self::unknown(url),
+            },
+            _ => Self::unknown(url),
+        }
+    }
+
+    /// Link tokens end up in backend paths, so only accept URL-safe characters
+    fn is_token(token: &str) -> bool {
+        !token.is_empty()
+            && token
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    fn unknown(url: &Url) -> DeepLinkRoute {
+        DeepLinkRoute::Unknown {
+            url: url.to_string(),
+        }
+    }
+}
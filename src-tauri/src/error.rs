@@ -1,80 +1,268 @@
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
 use std::fmt;
+use tauri_plugin_http::reqwest::StatusCode;
+
+/// Stable error codes the frontend can match on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidCredentials,
+    TokenExpired,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Conflict,
+    InvalidRequest,
+    PlatformNotConnected,
+    RateLimited,
+    ServerError,
+    Offline,
+    Timeout,
+    Cancelled,
+    Unknown,
+}
+
+/// Payload shared by every AppError variant
+#[derive(Debug, Clone)]
+pub struct ErrorDetails {
+    pub message: String,
+    pub code: Option<ErrorCode>,
+    pub status: Option<u16>,
+    /// Parsed backend error body, when the backend sent JSON
+    pub body: Option<Value>,
+    /// What the app was doing when the error happened
+    pub context: Option<String>,
+}
+
+impl From<String> for ErrorDetails {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            code: None,
+            status: None,
+            body: None,
+            context: None,
+        }
+    }
+}
+
+impl From<&str> for ErrorDetails {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
 
 /// Custom error type for the application
 #[derive(Debug)]
 pub enum AppError {
     /// Backend API errors
-    Backend(String),
+    Backend(ErrorDetails),
     /// Network-related errors
-    Network(String),
+    Network(ErrorDetails),
     /// Authentication errors
-    Auth(String),
+    Auth(ErrorDetails),
     /// Validation errors
-    Validation(String),
+    Validation(ErrorDetails),
     /// Resource not found
-    NotFound(String),
+    NotFound(ErrorDetails),
     /// Internal errors
-    Internal(String),
+    Internal(ErrorDetails),
+}
+
+impl AppError {
+    /// Build an error from a failed backend response
+    pub fn from_response(status: StatusCode, body_text: String) -> Self {
+        let body = serde_json::from_str::<Value>(&body_text).ok();
+        let message = body
+            .as_ref()
+            .and_then(|body| body.get("message").or_else(|| body.get("error")))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                if body_text.is_empty() {
+                    format!("Server error: {}", status)
+                } else {
+                    body_text
+                }
+            });
+        let backend_code = body
+            .as_ref()
+            .and_then(|body| body.get("code").cloned())
+            .and_then(|code| serde_json::from_value::<ErrorCode>(code).ok());
+
+        let details = ErrorDetails {
+            message,
+            code: backend_code,
+            status: Some(status.as_u16()),
+            body,
+            context: None,
+        };
+
+        match status {
+            StatusCode::UNAUTHORIZED => AppError::Auth(details),
+            StatusCode::FORBIDDEN => AppError::Auth(details.or_code(ErrorCode::Forbidden)),
+            StatusCode::NOT_FOUND => AppError::NotFound(details),
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+                AppError::Validation(details)
+            }
+            StatusCode::CONFLICT => AppError::Backend(details.or_code(ErrorCode::Conflict)),
+            StatusCode::TOO_MANY_REQUESTS => {
+                AppError::Backend(details.or_code(ErrorCode::RateLimited))
+            }
+            status if status.is_server_error() => AppError::Internal(details),
+            _ => AppError::Backend(details),
+        }
+    }
+
+    pub fn details(&self) -> &ErrorDetails {
+        match self {
+            AppError::Backend(details)
+            | AppError::Network(details)
+            | AppError::Auth(details)
+            | AppError::Validation(details)
+            | AppError::NotFound(details)
+            | AppError::Internal(details) => details,
+        }
+    }
+
+    fn details_mut(&mut self) -> &mut ErrorDetails {
+        match self {
+            AppError::Backend(details)
+            | AppError::Network(details)
+            | AppError::Auth(details)
+            | AppError::Validation(details)
+            | AppError::NotFound(details)
+            | AppError::Internal(details) => details,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            AppError::Backend(_) => "backend",
+            AppError::Network(_) => "network",
+            AppError::Auth(_) => "auth",
+            AppError::Validation(_) => "validation",
+            AppError::NotFound(_) => "not_found",
+            AppError::Internal(_) => "internal",
+        }
+    }
+
+    /// Error code, falling back to a default for the variant
+    pub fn code(&self) -> ErrorCode {
+        self.details().code.unwrap_or(match self {
+            AppError::Backend(_) => ErrorCode::Unknown,
+            AppError::Network(_) => ErrorCode::Offline,
+            AppError::Auth(details) if details.status == Some(401) => ErrorCode::TokenExpired,
+            AppError::Auth(_) => ErrorCode::Unauthorized,
+            AppError::Validation(_) => ErrorCode::InvalidRequest,
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::Internal(_) => ErrorCode::ServerError,
+        })
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        self.details().status
+    }
+
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.details_mut().code = Some(code);
+        self
+    }
+
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.details_mut().context = Some(context.into());
+        self
+    }
+}
+
+impl ErrorDetails {
+    fn or_code(mut self, code: ErrorCode) -> Self {
+        self.code.get_or_insert(code);
+        self
+    }
 }
 
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = &self.details().message;
         match self {
-            AppError::Backend(msg) => write!(f, "Backend error: {}", msg),
-            AppError::Network(msg) => write!(f, "Network error: {}", msg),
-            AppError::Auth(msg) => write!(f, "Authentication error: {}", msg),
-            AppError::Validation(msg) => write!(f, "Validation error: {}", msg),
-            AppError::NotFound(msg) => write!(f, "Not found: {}", msg),
-            AppError::Internal(msg) => write!(f, "Internal error: {}", msg),
+            AppError::Backend(_) => write!(f, "Backend error: {}", msg),
+            AppError::Network(_) => write!(f, "Network error: {}", msg),
+            AppError::Auth(_) => write!(f, "Authentication error: {}", msg),
+            AppError::Validation(_) => write!(f, "Validation error: {}", msg),
+            AppError::NotFound(_) => write!(f, "Not found: {}", msg),
+            AppError::Internal(_) => write!(f, "Internal error: {}", msg),
         }
     }
 }
 
 impl std::error::Error for AppError {}
 
+#[derive(Serialize)]
+struct SerializedError<'a> {
+    kind: &'static str,
+    code: ErrorCode,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<&'a Value>,
+}
+
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        let details = self.details();
+        SerializedError {
+            kind: self.kind(),
+            code: self.code(),
+            message: &details.message,
+            context: details.context.as_deref(),
+            status: details.status,
+            details: details.body.as_ref(),
+        }
+        .serialize(serializer)
     }
 }
 
 impl From<Box<dyn std::error::Error + Send + Sync>> for AppError {
     fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
-        AppError::Internal(err.to_string())
+        AppError::Internal(err.to_string().into())
     }
 }
 
 impl From<std::io::Error> for AppError {
     fn from(err: std::io::Error) -> Self {
-        AppError::Network(err.to_string())
+        AppError::Network(err.to_string().into())
     }
 }
 
 impl From<tauri_plugin_http::reqwest::Error> for AppError {
     fn from(err: tauri_plugin_http::reqwest::Error) -> Self {
         if err.is_timeout() {
-            AppError::Network("Request timeout".to_string())
+            AppError::Network("Request timeout".into()).with_code(ErrorCode::Timeout)
         } else if err.is_connect() {
-            AppError::Network("Connection failed".to_string())
+            AppError::Network("Connection failed".into()).with_code(ErrorCode::Offline)
         } else {
-            AppError::Network(err.to_string())
+            AppError::Network(err.to_string().into()).with_code(ErrorCode::Unknown)
         }
     }
 }
 
 impl From<serde_json::Error> for AppError {
     fn from(err: serde_json::Error) -> Self {
-        AppError::Internal(err.to_string())
+        AppError::Internal(err.to_string().into())
     }
 }
 
 impl From<tauri_plugin_musickit::Error> for AppError {
     fn from(err: tauri_plugin_musickit::Error) -> Self {
-        AppError::Internal(err.to_string())
+        AppError::Internal(err.to_string().into())
     }
 }
 
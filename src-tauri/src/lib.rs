@@ -1,10 +1,12 @@
 mod app;
 mod backend;
 mod commands;
+mod deep_link;
 mod error;
 mod models;
+mod utils;
 use log::info;
-use tauri::{async_runtime::spawn, Builder, Emitter, Manager, Wry};
+use tauri::{async_runtime::spawn, Builder, Manager, Wry};
 use tauri_plugin_log::{Target, TargetKind};
 
 use app::App;
@@ -21,6 +23,8 @@ pub fn run() {
             Target::new(TargetKind::Webview),
         ])
         .level(log::LevelFilter::Info)
+        // Every target gets the redacted line, secrets never reach stdout or the webview
+        .format(utils::log_format)
         .build();
     builder = builder
         .plugin(tauri_plugin_haptics::init())
@@ -86,13 +90,7 @@ fn finish_setup(builder: Builder<Wry>) {
                 });
             });
 
-            app_handle.manage(swaptun_app.clone());
-
-            spawn(async move {
-                swaptun_app.set_app_ready().await;
-
-                app_handle.emit("app_ready", "").unwrap();
-            });
+            app_handle.manage(swaptun_app);
 
             Ok(())
         })
@@ -101,13 +99,17 @@ fn finish_setup(builder: Builder<Wry>) {
             login,
             login_email,
             verify_token,
-            get_autorization_url_spotify,
             is_app_ready,
-            test_spotify,
-            get_playlists_spotify,
-            get_playlists_deezer,
-            connect_youtube,
-            get_playlists_youtubemusic,
+            set_app_ready,
+            cancel_request,
+            get_connectivity_status,
+            get_backend_environment,
+            set_backend_environment,
+            connect_platform,
+            import_playlists,
+            get_playlists,
+            disconnect_platform,
+            get_platform_connections,
             set_fcm_token,
             check_opening_notification,
             send_playlist,
@@ -117,18 +119,25 @@ fn finish_setup(builder: Builder<Wry>) {
             check_opening_url,
             search_users,
             get_friends,
-            add_friend,
+            send_friend_request,
+            get_friend_requests,
+            accept_friend_request,
+            decline_friend_request,
+            cancel_friend_request,
+            block_user,
+            unblock_user,
+            get_blocked_users,
+            report_user,
             remove_friend,
+            create_friend_invite,
             search_non_friends_users,
-            connect_apple_music,
-            get_apple_music_playlists,
-            disconnect_spotify,
-            disconnect_youtube,
-            disconnect_apple_music,
             share_playlist,
             get_shared_playlists,
             mark_shared_playlist_viewed,
             get_playlist_musics,
+            create_playlist_share_link,
+            revoke_playlist_share_link,
+            open_pending_share_link,
             open_external_app
         ])
         .run(tauri::generate_context!())
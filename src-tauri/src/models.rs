@@ -1,3 +1,4 @@
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Deserializer, Serialize};
 use tauri_plugin_push_notifications::NotificationDataTrait;
 
@@ -9,11 +10,18 @@ pub struct SharedNotificationData {
     pub shared_by_username: String,
 }
 
-fn deserialize_shared_notification<'de, D>(
-    deserializer: D,
-) -> Result<Option<SharedNotificationData>, D::Error>
+/// Incoming friend request, sent with notifications of type `friend_request`
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FriendRequestNotificationData {
+    pub request_id: i32,
+    pub from_user_id: i32,
+    pub from_username: String,
+}
+
+fn deserialize_embedded_json<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
 where
     D: Deserializer<'de>,
+    T: DeserializeOwned,
 {
     use serde::de::{Error, Unexpected};
     use serde_json::Value;
@@ -24,21 +32,19 @@ where
     match val {
         Some(Value::String(s)) => {
             // Cas 1 : c’est une string contenant du JSON
-            serde_json::from_str::<SharedNotificationData>(&s)
+            serde_json::from_str::<T>(&s)
                 .map(Some)
-                .map_err(|e| {
-                    Error::custom(format!("Failed to parse shared_notification string: {}", e))
-                })
+                .map_err(|e| Error::custom(format!("Failed to parse notification string: {}", e)))
         }
         Some(obj @ Value::Object(_)) => {
             // Cas 2 : c’est déjà un objet JSON
-            serde_json::from_value(obj).map(Some).map_err(|e| {
-                Error::custom(format!("Failed to parse shared_notification object: {}", e))
-            })
+            serde_json::from_value(obj)
+                .map(Some)
+                .map_err(|e| Error::custom(format!("Failed to parse notification object: {}", e)))
         }
         Some(other) => Err(Error::invalid_type(
             Unexpected::Other(&format!("{:?}", other)),
-            &"string or object for shared_notification",
+            &"string or object for notification data",
         )),
         None => Ok(None),
     }
@@ -52,9 +58,15 @@ pub struct Notification {
     pub route: Option<String>,
     #[serde(
         skip_serializing_if = "Option::is_none",
-        deserialize_with = "deserialize_shared_notification"
+        deserialize_with = "deserialize_embedded_json"
     )]
     pub shared_notification: Option<SharedNotificationData>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_embedded_json"
+    )]
+    pub friend_request: Option<FriendRequestNotificationData>,
 }
 
 impl Notification {
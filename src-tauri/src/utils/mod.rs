@@ -1,3 +1,7 @@
+pub mod qr;
+pub mod redact;
 pub mod response;
 
+pub use qr::*;
+pub use redact::*;
 pub use response::*;
@@ -0,0 +1,42 @@
+use crate::error::{AppError, AppResult};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use image::{ImageFormat, Luma};
+use qrcode::render::svg;
+use qrcode::QrCode;
+use serde::Serialize;
+use std::io::Cursor;
+
+const QR_MIN_SIZE: u32 = 512;
+
+/// QR code of a link, ready to drop into an `<img>` or inline in the page
+#[derive(Debug, Clone, Serialize)]
+pub struct QrCodeImages {
+    pub svg: String,
+    /// `data:image/png;base64,...` URL
+    pub png: String,
+}
+
+pub fn qr_code_images(content: &str) -> AppResult<QrCodeImages> {
+    let code = QrCode::new(content.as_bytes())
+        .map_err(|e| AppError::Internal(format!("Failed to encode QR code: {}", e).into()))?;
+
+    let svg = code
+        .render::<svg::Color>()
+        .min_dimensions(QR_MIN_SIZE, QR_MIN_SIZE)
+        .build();
+
+    let image = code
+        .render::<Luma<u8>>()
+        .min_dimensions(QR_MIN_SIZE, QR_MIN_SIZE)
+        .build();
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(|e| AppError::Internal(format!("Failed to render QR code: {}", e).into()))?;
+
+    Ok(QrCodeImages {
+        svg,
+        png: format!("data:image/png;base64,{}", STANDARD.encode(png)),
+    })
+}
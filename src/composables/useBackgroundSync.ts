@@ -100,8 +100,8 @@ export function useBackgroundSync() {
       case 'share_playlist':
         await invoke('share_playlist', operation.data)
         break
-      case 'add_friend':
-        await invoke('add_friend', operation.data)
+      case 'send_friend_request':
+        await invoke('send_friend_request', operation.data)
         break
       case 'remove_friend':
         await invoke('remove_friend', operation.data)
@@ -1,72 +1,73 @@
+import { ref } from "vue";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { info } from "@tauri-apps/plugin-log";
+import type { PlatformConnection, PlaylistOrigin } from "@/models/playlist";
+import { PLATFORMS } from "@/utils/constants";
 
-export type Platform = "spotify" | "youtube" | "deezer" | "appleMusic";
+export type Platform = (typeof PLATFORMS)[number]["id"];
+
+// Backend origin of each platform shown in the UI
+export const PLATFORM_ORIGINS = Object.fromEntries(
+  PLATFORMS.map((platform) => [platform.id, platform.origin])
+) as Record<Platform, PlaylistOrigin>;
 
 export function usePlatformConnect() {
-  const connectToSpotify = async () => {
+  const connectToPlatform = async (platform: Platform) => {
+    const origin = PLATFORM_ORIGINS[platform];
+    if (!origin) {
+      throw new Error(`Unknown platform: ${platform}`);
+    }
     try {
-      info("Connecting to Spotify...");
-      await invoke("get_autorization_url_spotify");
+      info(`Connecting to ${origin}...`);
+      await invoke("connect_platform", { origin });
     } catch (error) {
-      info("Error connecting to Spotify: " + error);
+      info(`Error connecting to ${origin}: ` + error);
       throw error;
     }
   };
 
-  const connectToYoutube = async () => {
-    try {
-      info("Connecting to YouTube Music...");
-      await invoke("connect_youtube");
-    } catch (error) {
-      info("Error connecting to YouTube: " + error);
-      throw error;
-    }
+  return {
+    connectToPlatform,
   };
+}
 
-  const connectToDeezer = async () => {
+// Connection of each platform, kept up to date by platform_connection_changed
+export function usePlatformConnections() {
+  const connections = ref<Partial<Record<PlaylistOrigin, PlatformConnection>>>({});
+
+  let unlisten: (() => void) | null = null;
+
+  const fetchConnections = async () => {
     try {
-      info("Connecting to Deezer...");
-      // TODO: Implement Deezer connection when available
-      throw new Error("Deezer connection not yet implemented");
+      const list = await invoke<PlatformConnection[]>("get_platform_connections");
+      connections.value = Object.fromEntries(list.map((c) => [c.origin, c]));
     } catch (error) {
-      info("Error connecting to Deezer: " + error);
-      throw error;
+      info("Error fetching platform connections: " + error);
     }
   };
 
-  const connectToAppleMusic = async () => {
-    try {
-      info("Connecting to Apple Music...");
-      const response = await invoke("connect_apple_music");
-      info("Apple Music authorization response: " + JSON.stringify(response));
-      return response;
-    } catch (error) {
-      info("Error connecting to Apple Music: " + error);
-      throw error;
-    }
+  const setupConnectionListener = async () => {
+    unlisten = await listen<PlatformConnection>(
+      "platform_connection_changed",
+      (event) => {
+        connections.value = {
+          ...connections.value,
+          [event.payload.origin]: event.payload,
+        };
+      }
+    );
   };
 
-  const connectToPlatform = async (platform: Platform) => {
-    switch (platform) {
-      case "spotify":
-        return await connectToSpotify();
-      case "youtube":
-        return await connectToYoutube();
-      case "deezer":
-        return await connectToDeezer();
-      case "appleMusic":
-        return await connectToAppleMusic();
-      default:
-        throw new Error(`Unknown platform: ${platform}`);
-    }
+  const cleanup = () => {
+    unlisten?.();
+    unlisten = null;
   };
 
   return {
-    connectToSpotify,
-    connectToYoutube,
-    connectToDeezer,
-    connectToAppleMusic,
-    connectToPlatform,
+    connections,
+    fetchConnections,
+    setupConnectionListener,
+    cleanup,
   };
 }
@@ -2,8 +2,10 @@ import { ref, computed } from "vue";
 import { invoke } from "@tauri-apps/api/core";
 import { useUserStore } from "@/store/user";
 import { useAppStore } from "@/store/app";
+import type { PlaylistOrigin } from "@/models/playlist";
+import { PLATFORMS } from "@/utils/constants";
 
-export type Platform = "Spotify" | "YoutubeMusic" | "AppleMusic" | "Deezer";
+export type Platform = PlaylistOrigin;
 
 export interface ConnectedPlatform {
   name: Platform;
@@ -23,44 +25,17 @@ export function useSendPlaylist() {
   const error = ref<string | null>(null);
 
   // Detect connected platforms based on whether user has playlists from that platform
-  const connectedPlatforms = computed<ConnectedPlatform[]>(() => {
-    const platforms: ConnectedPlatform[] = [];
-
-    if (userStore.spotifyPlaylists.length > 0) {
-      platforms.push({
-        name: "Spotify",
-        label: "Spotify",
-        icon: "/src/assets/images/spotify.svg",
-      });
-    }
-
-    if (userStore.youtubePlaylists.length > 0) {
-      platforms.push({
-        name: "YoutubeMusic",
-        label: "YouTube Music",
-        icon: "/src/assets/images/Youtube_Music_icon.svg.png",
-      });
-    }
-
-    if (userStore.applePlaylists.length > 0) {
-      platforms.push({
-        name: "AppleMusic",
-        label: "Apple Music",
-        icon: "/src/assets/images/Apple_Music_icon.svg.png",
-      });
-    }
-
-    // Deezer is not yet implemented for sending
-    // if (userStore.deezerPlaylists.length > 0) {
-    //   platforms.push({
-    //     name: "Deezer",
-    //     label: "Deezer",
-    //     icon: "/src/assets/images/deezer.svg",
-    //   });
-    // }
-
-    return platforms;
-  });
+  const connectedPlatforms = computed<ConnectedPlatform[]>(() =>
+    PLATFORMS.filter(
+      (platform) =>
+        platform.canReceive &&
+        userStore.playlistsOf(platform.origin).length > 0
+    ).map((platform) => ({
+      name: platform.origin,
+      label: platform.name,
+      icon: platform.icon,
+    }))
+  );
 
   const hasConnectedPlatforms = computed(() => connectedPlatforms.value.length > 0);
   const hasSinglePlatform = computed(() => connectedPlatforms.value.length === 1);
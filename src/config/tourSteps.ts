@@ -22,7 +22,7 @@ export const tourSteps: TourStep[] = [
   {
     id: 'connect-platform',
     title: 'Connect your music platform',
-    description: 'Start by connecting your favorite music streaming service. You can connect Spotify, Apple Music, YouTube Music, or Deezer.',
+    description: 'Start by connecting your favorite music streaming service. You can connect Spotify, Apple Music, YouTube Music, Deezer, Tidal, SoundCloud, or Amazon Music.',
     target: '[data-tour="platform-button"]',
     placement: 'bottom',
     route: '/home',
@@ -37,6 +37,12 @@ listen<string>("routing", async (event) => {
   router.push(route);
 });
 
+// The session could not be refreshed, the user has to log in again
+listen("session_expired", async () => {
+  userStore.reset();
+  await router.replace("/login");
+});
+
 await router.isReady();
 
 // Check if app was opened from notification or URL
@@ -44,7 +50,7 @@ await invoke("check_opening_notification");
 await invoke("check_opening_url");
 
 // Fetch shared playlists and friends at app start if user is authenticated
-if (userStore.token) {
+if (userStore.authenticated) {
   sharedPlaylistsStore.fetchSharedPlaylists(true).catch((error) => {
     console.error("Failed to fetch shared playlists at app start:", error);
   });
@@ -56,5 +62,8 @@ if (userStore.token) {
 
 app.mount("#app");
 
+// Release deep links queued while the app was booting, readiness outlives sessions
+await invoke("set_app_ready");
+
 console.log("après mount");
 
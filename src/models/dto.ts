@@ -1,5 +1,4 @@
 export default interface LoginResponse {
-  token: string;
   user: {
     id: number;
     username: string;
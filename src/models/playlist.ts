@@ -1,4 +1,5 @@
 import Music from "@/models/music"
+import type { PLATFORMS } from "@/utils/constants";
 
 // Frontend Playlist structure (simplified)
 export default interface Playlist {
@@ -22,6 +23,31 @@ export interface GetPlaylistResponse {
   playlists: Playlist[];
 }
 
+// Backend PlaylistOrigin, as accepted by the platform commands
+export type PlaylistOrigin = (typeof PLATFORMS)[number]["origin"];
+
+// Payload of the platform_playlists event
+export interface PlatformPlaylists {
+  origin: PlaylistOrigin;
+  playlists: GetPlaylistResponse;
+}
+
+export type ConnectionState =
+  | "connected"
+  | "expired"
+  | "revoked"
+  | "disconnected"
+  | "unknown";
+
+// Result of get_platform_connections and payload of the platform_connection_changed event
+export interface PlatformConnection {
+  origin: PlaylistOrigin;
+  state: ConnectionState;
+  display_name: string | null;
+  last_synced_at: string | null;
+  scopes: string[];
+}
+
 // Legacy structure (for backwards compatibility)
 export interface PlaylistsResponse {
   playlists: Playlist[];
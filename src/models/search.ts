@@ -0,0 +1,5 @@
+// One page of search results, pass nextCursor back to get the following one
+export interface SearchPage<T = any> {
+  items: T[];
+  nextCursor: string | null;
+}
@@ -1,20 +1,24 @@
-import  Playlist from "@/models/playlist";
+import Playlist, { PlaylistOrigin } from "@/models/playlist";
 
 export default interface User {
   id: number;
   username: string;
 }
 
+// One page of a user search, pass next_cursor back to get the next one
+export interface UserPage {
+  users: User[];
+  next_cursor: string | null;
+  total: number;
+}
+
 // Interface for the user store state
 export interface UserState {
   id: number | null;
-  token: string | null;
+  authenticated: boolean;
   information_loaded: boolean;
   username: string | null;
   isFirstLogin: boolean;
-  youtubePlaylists: Playlist[];
-  deezerPlaylists: Playlist[];
-  spotifyPlaylists: Playlist[];
-  applePlaylists: Playlist[];
+  playlists: Partial<Record<PlaylistOrigin, Playlist[]>>;
   friends: User[];
 }
@@ -77,7 +77,7 @@ router.beforeEach((to, from, next) => {
   if (to.meta.requiresQuery && Object.keys(to.query).length === 0) {
     console.log("Route requires query parameters, redirecting to home");
     next({ path: "/" });
-  } else if (to.meta.requiresAuth && userStore.token == null) {
+  } else if (to.meta.requiresAuth && !userStore.authenticated) {
     console.log("User is not authenticated, redirecting to login");
     next({ name: "Login" });
   } else {
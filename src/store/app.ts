@@ -1,4 +1,5 @@
 import { defineStore } from 'pinia';
+import type { PlaylistOrigin } from '@/models/playlist';
 
 export interface PlaylistSendSuccess {
   platformLabel: string;
@@ -9,10 +10,9 @@ export interface PlaylistSendSuccess {
 
 export interface AppState {
   isAppReady: boolean;
-  isLoadingYouTube: boolean;
-  isLoadingDeezer: boolean;
-  isLoadingSpotify: boolean;
-  isLoadingApple: boolean;
+  // Playlist fetches in flight and their last error, by platform
+  loadingPlatforms: Partial<Record<PlaylistOrigin, boolean>>;
+  platformErrors: Partial<Record<PlaylistOrigin, string | null>>;
   isSendingPlaylist: boolean;
   playlistSendSuccess: PlaylistSendSuccess | null;
 }
@@ -20,10 +20,8 @@ export interface AppState {
 export const useAppStore = defineStore('app', {
   state: (): AppState => ({
     isAppReady: false,
-    isLoadingYouTube: false,
-    isLoadingDeezer: false,
-    isLoadingSpotify: false,
-    isLoadingApple: false,
+    loadingPlatforms: {},
+    platformErrors: {},
     isSendingPlaylist: false,
     playlistSendSuccess: null,
   }),
@@ -31,11 +29,11 @@ export const useAppStore = defineStore('app', {
     setAppReady(isReady: boolean) {
       this.isAppReady = isReady;
     },
-    setLoading(service: 'youtube' | 'deezer' | 'spotify' | 'apple', isLoading: boolean) {
-      if (service === 'youtube') this.isLoadingYouTube = isLoading;
-      if (service === 'deezer') this.isLoadingDeezer = isLoading;
-      if (service === 'spotify') this.isLoadingSpotify = isLoading;
-      if (service === 'apple') this.isLoadingApple = isLoading;
+    setLoading(origin: PlaylistOrigin, isLoading: boolean) {
+      this.loadingPlatforms = { ...this.loadingPlatforms, [origin]: isLoading };
+    },
+    setPlatformError(origin: PlaylistOrigin, error: string | null) {
+      this.platformErrors = { ...this.platformErrors, [origin]: error };
     },
     setSendingPlaylist(isSending: boolean) {
       this.isSendingPlaylist = isSending;
@@ -44,6 +42,10 @@ export const useAppStore = defineStore('app', {
       this.playlistSendSuccess = success;
     },
   },
+  getters: {
+    isLoading: (state) => (origin: PlaylistOrigin): boolean =>
+      state.loadingPlatforms[origin] ?? false,
+  },
   tauri: {
     saveOnChange: true,
   },
@@ -1,19 +1,16 @@
 import { defineStore } from 'pinia';
-import Playlist from '@/models/playlist';
+import Playlist, { PlaylistOrigin } from '@/models/playlist';
 import User, { UserState } from '@/models/user';
 import { invoke } from '@tauri-apps/api/core';
 import { info } from '@tauri-apps/plugin-log';
 export const useUserStore = defineStore('user', {
   state: (): UserState => ({
     id: null,
-    token: null,
+    authenticated: false,
     username: null,
     information_loaded: false,
     isFirstLogin: false,
-    youtubePlaylists: [],
-    deezerPlaylists: [],
-    spotifyPlaylists: [],
-    applePlaylists: [],
+    playlists: {},
     friends: [],
   }),
   actions: {
@@ -23,8 +20,8 @@ export const useUserStore = defineStore('user', {
       this.information_loaded = true;
       this.isFirstLogin = isFirstLogin;
     },
-    setToken(token: string) {
-      this.token = token;
+    setAuthenticated(authenticated: boolean) {
+      this.authenticated = authenticated;
     },
     markTourSeen() {
       this.isFirstLogin = false;
@@ -39,42 +36,26 @@ export const useUserStore = defineStore('user', {
         throw error;
       }
     },
-    setYoutubePlaylists(playlists: Playlist[]) {
-      console.log("Setting YouTube playlists:", playlists);
-      this.youtubePlaylists = playlists;
-    },
-    setDeezerPlaylists(playlists: Playlist[]) {
-      console.log("Setting Deezer playlists:", playlists);
-      this.deezerPlaylists = playlists;
-    },
-    setSpotifyPlaylists(playlists: Playlist[]) {
-      console.log("Setting Spotify playlists:", playlists);
-      this.spotifyPlaylists = playlists;
-    },
-    setApplePlaylists(playlists: Playlist[]) {
-      console.log("Setting Apple playlists:", playlists);
-      this.applePlaylists = playlists;
+    setPlaylists(origin: PlaylistOrigin, playlists: Playlist[]) {
+      console.log(`Setting ${origin} playlists:`, playlists);
+      this.playlists = { ...this.playlists, [origin]: playlists };
     },
     reset() {
       // Reset all state values to their defaults
       this.id = null;
-      this.token = null;
+      this.authenticated = false;
       this.username = null;
       this.information_loaded = false;
-      this.youtubePlaylists = [];
-      this.deezerPlaylists = [];
-      this.spotifyPlaylists = [];
-      this.applePlaylists = [];
+      this.playlists = {};
       this.friends = [];
     },
-    addFriend(user:User){
-      invoke("add_friend", { request: {friend_id: user.id} })
+    sendFriendRequest(user:User){
+      invoke("send_friend_request", { userId: user.id })
         .then(() => {
-          info("Friend added successfully");
-          this.friends.push(user);
+          info("Friend request sent successfully");
         })
         .catch((error) => {
-          info("Error adding friend: " + error);
+          info("Error sending friend request: " + error);
         });
     },
     removeFriend(user:User){
@@ -89,8 +70,10 @@ export const useUserStore = defineStore('user', {
     }
   },
   getters: {
+    playlistsOf: (state) => (origin: PlaylistOrigin): Playlist[] =>
+      state.playlists[origin] ?? [],
     allPlaylists: (state): Playlist[] => {
-      return [...state.youtubePlaylists, ...state.deezerPlaylists, ...state.spotifyPlaylists, ...state.applePlaylists];
+      return Object.values(state.playlists).flatMap((playlists) => playlists ?? []);
     },
   },
   tauri: {
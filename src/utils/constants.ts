@@ -1,18 +1,54 @@
+// Every supported platform, adding one here makes it show up across the app.
+// canReceive marks the platforms playlists can be sent to.
 export const PLATFORMS = [
   {
     id: "spotify",
+    origin: "Spotify",
     name: "Spotify",
     icon: "/src/assets/images/spotify.svg",
+    canReceive: true,
   },
   {
     id: "appleMusic",
+    origin: "AppleMusic",
     name: "Apple Music",
     icon: "/src/assets/images/Apple_Music_icon.svg.png",
+    canReceive: true,
   },
   {
     id: "youtube",
+    origin: "YoutubeMusic",
     name: "YouTube Music",
     icon: "/src/assets/images/Youtube_Music_icon.svg.png",
+    canReceive: true,
+  },
+  {
+    id: "deezer",
+    origin: "Deezer",
+    name: "Deezer",
+    icon: "/src/assets/images/deezer.svg",
+    canReceive: false,
+  },
+  {
+    id: "tidal",
+    origin: "Tidal",
+    name: "Tidal",
+    icon: "/src/assets/images/tidal.svg",
+    canReceive: true,
+  },
+  {
+    id: "soundcloud",
+    origin: "SoundCloud",
+    name: "SoundCloud",
+    icon: "/src/assets/images/soundcloud.svg",
+    canReceive: true,
+  },
+  {
+    id: "amazonMusic",
+    origin: "AmazonMusic",
+    name: "Amazon Music",
+    icon: "/src/assets/images/amazon_music.svg",
+    canReceive: true,
   },
 ] as const;
 